@@ -0,0 +1,261 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    answers::Answers,
+    diagnostics,
+    record::{solve_part, Outcome},
+};
+use eyre::eyre;
+
+use crate::{
+    day_dir, days::DAYS, parse_input, read_input, report, InputSource, Options, Selection,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Csv,
+}
+
+pub struct BenchOptions {
+    pub repeat: usize,
+    pub report: ReportFormat,
+    pub output: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> BenchOptions {
+        BenchOptions {
+            repeat: 5,
+            report: ReportFormat::Markdown,
+            output: None,
+            baseline: None,
+        }
+    }
+}
+
+// All the timings for one phase (parse, part 1 or part 2) of one day's input.
+struct PhaseTimings {
+    day: usize,
+    input: String,
+    phase: String,
+    runs: Vec<Duration>,
+}
+
+impl PhaseTimings {
+    fn min(&self) -> Duration {
+        self.runs.iter().min().copied().unwrap_or_default()
+    }
+
+    fn median(&self) -> Duration {
+        let mut runs = self.runs.clone();
+        runs.sort();
+        runs.get(runs.len() / 2).copied().unwrap_or_default()
+    }
+
+    fn mean(&self) -> Duration {
+        if self.runs.is_empty() {
+            return Duration::ZERO;
+        }
+        self.runs.iter().sum::<Duration>() / self.runs.len() as u32
+    }
+
+    fn key(&self) -> (usize, String, String) {
+        (self.day, self.input.clone(), self.phase.clone())
+    }
+}
+
+// Times every recorded input and part for a day. Only parts with a recorded
+// answer are run, which keeps out the ones that are still too slow to finish.
+fn bench_day(
+    day: usize,
+    parts: &[u8],
+    only: &str,
+    repeat: usize,
+) -> eyre::Result<Vec<PhaseTimings>> {
+    let (Some(entry), Some(answers)) = (
+        &DAYS[day - 1],
+        Answers::load(&day_dir(day).join("answers.toml"))?,
+    ) else {
+        return Ok(Vec::new());
+    };
+    let mut timings = Vec::new();
+    for input in answers.inputs().filter(|i| *i == only) {
+        let expected: Vec<(u8, &str)> = parts
+            .iter()
+            .filter_map(|part| Some((*part, answers.expected(input, *part)?)))
+            .collect();
+        if expected.is_empty() {
+            continue;
+        }
+        eprintln!("Benchmarking day {day} {input}");
+        let text = read_input(&day_dir(day).join(format!("{input}.txt")))?;
+        let mut parse_runs = Vec::new();
+        let mut part_runs: Vec<Vec<Duration>> = vec![Vec::new(); expected.len()];
+        for _ in 0..repeat {
+            let start = Instant::now();
+            let (parsed, _) =
+                diagnostics::capture(|| parse_input(entry, &text, answers.params(input)));
+            parse_runs.push(start.elapsed());
+            let parsed = parsed?;
+            for ((part, expected), runs) in expected.iter().zip(part_runs.iter_mut()) {
+                let (outcome, _) = diagnostics::capture(|| solve_part(*part, parsed.as_ref()));
+                match outcome {
+                    Outcome::Answer(answer, duration) => {
+                        if answer.trim() != expected.trim() {
+                            eprintln!("Warning: day {day} {input} part {part} gave {answer}, expected {expected}");
+                        }
+                        runs.push(duration);
+                    }
+                    Outcome::NotImplemented => {}
+                    Outcome::Failed(e) => {
+                        return Err(eyre!("Day {day} {input} part {part} failed: {e}"))
+                    }
+                }
+            }
+        }
+        timings.push(PhaseTimings {
+            day,
+            input: input.to_owned(),
+            phase: "parse".to_owned(),
+            runs: parse_runs,
+        });
+        for ((part, _), runs) in expected.into_iter().zip(part_runs) {
+            if !runs.is_empty() {
+                timings.push(PhaseTimings {
+                    day,
+                    input: input.to_owned(),
+                    phase: format!("part{part}"),
+                    runs,
+                });
+            }
+        }
+    }
+    Ok(timings)
+}
+
+// Median times in nanoseconds from an earlier CSV report.
+fn load_baseline(path: &Path) -> eyre::Result<BTreeMap<(usize, String, String), u128>> {
+    let text = read_input(path)?;
+    let records = report::parse_csv(&text);
+    let (header, records) = records
+        .split_first()
+        .ok_or_else(|| eyre!("{} is empty", path.display()))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| eyre!("{} has no {name} column", path.display()))
+    };
+    let (day, input, phase, median) = (
+        column("day")?,
+        column("input")?,
+        column("phase")?,
+        column("median_ns")?,
+    );
+    let mut baseline = BTreeMap::new();
+    for record in records {
+        let field = |i: usize| {
+            record
+                .get(i)
+                .ok_or_else(|| eyre!("Short record in {}: {record:?}", path.display()))
+        };
+        baseline.insert(
+            (
+                field(day)?.parse()?,
+                field(input)?.clone(),
+                field(phase)?.clone(),
+            ),
+            field(median)?.parse()?,
+        );
+    }
+    Ok(baseline)
+}
+
+fn render(
+    timings: &[PhaseTimings],
+    baseline: Option<&BTreeMap<(usize, String, String), u128>>,
+    format: ReportFormat,
+) -> String {
+    let mut header = vec!["day", "input", "phase", "runs"];
+    match format {
+        ReportFormat::Markdown => header.extend(["min", "median", "mean"]),
+        ReportFormat::Csv => header.extend(["min_ns", "median_ns", "mean_ns"]),
+    }
+    if baseline.is_some() {
+        match format {
+            ReportFormat::Markdown => header.extend(["baseline", "change"]),
+            ReportFormat::Csv => header.extend(["baseline_median_ns", "change_percent"]),
+        }
+    }
+    let rows: Vec<Vec<String>> = timings
+        .iter()
+        .map(|t| {
+            let mut row = vec![
+                t.day.to_string(),
+                t.input.clone(),
+                t.phase.clone(),
+                t.runs.len().to_string(),
+            ];
+            let stats = [t.min(), t.median(), t.mean()];
+            row.extend(stats.iter().map(|d| match format {
+                ReportFormat::Markdown => format!("{d:?}"),
+                ReportFormat::Csv => d.as_nanos().to_string(),
+            }));
+            if let Some(baseline) = baseline {
+                match baseline.get(&t.key()) {
+                    Some(before) => {
+                        let change = (t.median().as_nanos() as f64 - *before as f64)
+                            / (*before).max(1) as f64
+                            * 100.0;
+                        row.push(match format {
+                            ReportFormat::Markdown => {
+                                format!("{:?}", Duration::from_nanos(*before as u64))
+                            }
+                            ReportFormat::Csv => before.to_string(),
+                        });
+                        row.push(format!("{change:+.1}%"));
+                    }
+                    None => row.extend([String::new(), String::new()]),
+                }
+            }
+            row
+        })
+        .collect();
+    match format {
+        ReportFormat::Markdown => report::markdown_table(&header, &rows),
+        ReportFormat::Csv => report::csv(&header, &rows),
+    }
+}
+
+pub fn bench(options: &Options, parts: &[u8]) -> eyre::Result<()> {
+    let bench = &options.bench;
+    let only = match options.input {
+        Some(InputSource::Demo) => "demo",
+        _ => "input",
+    };
+    let days = match options.selection {
+        Selection::Day(day) => day..=day,
+        Selection::All => 1..=DAYS.len(),
+    };
+    let baseline = bench.baseline.as_deref().map(load_baseline).transpose()?;
+    let mut timings = Vec::new();
+    for day in days {
+        timings.extend(bench_day(day, parts, only, bench.repeat)?);
+    }
+    let mut report = render(&timings, baseline.as_ref(), bench.report);
+    report.push('\n');
+    match &bench.output {
+        Some(path) => {
+            fs::write(path, report).map_err(|e| eyre!("Failed to write {}: {e}", path.display()))?
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
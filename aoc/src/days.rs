@@ -0,0 +1,47 @@
+use aoc_common::{parse, Param, Params, Parsed, Solver};
+
+pub type ParseFn = fn(&str, &Params) -> eyre::Result<Box<dyn Parsed>>;
+
+pub struct Day {
+    pub parse: ParseFn,
+    pub params: &'static [Param],
+}
+
+const fn day<S>() -> Day
+where
+    S: Solver + 'static,
+    S::Input: 'static,
+{
+    Day {
+        parse: parse::<S>,
+        params: S::PARAMS,
+    }
+}
+
+pub const DAYS: [Option<Day>; 25] = [
+    Some(day::<day1::Day1>()),
+    Some(day::<day2::Day2>()),
+    Some(day::<day3::Day3>()),
+    Some(day::<day4::Day4>()),
+    Some(day::<day5::Day5>()),
+    Some(day::<day6::Day6>()),
+    Some(day::<day7::Day7>()),
+    Some(day::<day8::Day8>()),
+    Some(day::<day9::Day9>()),
+    Some(day::<day10::Day10>()),
+    Some(day::<day11::Day11>()),
+    Some(day::<day12::Day12>()),
+    Some(day::<day13::Day13>()),
+    Some(day::<day14::Day14>()),
+    Some(day::<day15::Day15>()),
+    Some(day::<day16::Day16>()),
+    Some(day::<day17::Day17>()),
+    Some(day::<day18::Day18>()),
+    Some(day::<day19::Day19>()),
+    Some(day::<day20::Day20>()),
+    Some(day::<day21::Day21>()),
+    Some(day::<day22::Day22>()),
+    Some(day::<day23::Day23>()),
+    Some(day::<day24::Day24>()),
+    Some(day::<day25::Day25>()),
+];
@@ -0,0 +1,501 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    answers::Answers,
+    diagnostics,
+    record::{json_record, solve_part, Format, Outcome, RecordContext},
+    Params, Parsed,
+};
+use eyre::eyre;
+
+mod bench;
+mod days;
+mod report;
+
+use bench::{BenchOptions, ReportFormat};
+use days::DAYS;
+
+const USAGE: &str =
+    "Usage: aoc run <day|all> [--part 1|2] [--input demo|input|<path>] [--format text|json]
+       aoc verify [<day|all>] [--input demo|input]
+       aoc bench [<day|all>] [--input demo|input] [--repeat <n>] [--report markdown|csv] [--output <path>] [--baseline <csv>]";
+
+enum Selection {
+    Day(usize),
+    All,
+}
+
+enum InputSource {
+    Demo,
+    Input,
+    Path(PathBuf),
+}
+
+enum Command {
+    Run,
+    Verify,
+    Bench,
+}
+
+struct Options {
+    command: Command,
+    selection: Selection,
+    part: Option<u8>,
+    // Verify defaults to checking every recorded input rather than just the real one.
+    input: Option<InputSource>,
+    format: Format,
+    // Any other `--<name> <value>` options, for the day's parameters.
+    params: Vec<(String, String)>,
+    bench: BenchOptions,
+}
+
+fn parse_selection(day: &str) -> eyre::Result<Selection> {
+    if day == "all" {
+        return Ok(Selection::All);
+    }
+    let day: usize = day
+        .parse()
+        .map_err(|_| eyre!("Invalid day: {day}\n{USAGE}"))?;
+    if day == 0 || day > DAYS.len() {
+        return Err(eyre!("Day must be between 1 and {}", DAYS.len()));
+    }
+    Ok(Selection::Day(day))
+}
+
+fn parse_args(args: &[String]) -> eyre::Result<Options> {
+    let mut args = args.iter().peekable();
+    let command = match args.next().map(|s| s.as_str()) {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some("bench") => Command::Bench,
+        Some(cmd) => return Err(eyre!("Unexpected command: {cmd}\n{USAGE}")),
+        None => return Err(eyre!("{USAGE}")),
+    };
+    let selection = match (&command, args.peek()) {
+        (Command::Verify | Command::Bench, None) => Selection::All,
+        (Command::Verify | Command::Bench, Some(arg)) if arg.starts_with("--") => Selection::All,
+        (_, Some(_)) => parse_selection(args.next().unwrap())?,
+        (Command::Run, None) => return Err(eyre!("Missing day\n{USAGE}")),
+    };
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut params = Vec::new();
+    let mut bench = BenchOptions::default();
+    let is_bench = matches!(command, Command::Bench);
+    let declared = match selection {
+        Selection::Day(day) => DAYS[day - 1].as_ref().map_or(&[][..], |d| d.params),
+        Selection::All => &[],
+    };
+    while let Some(arg) = args.next() {
+        // A switch given without a value is turned on.
+        let is_flag = || {
+            declared
+                .iter()
+                .any(|p| Some(p.name) == arg.strip_prefix("--") && p.is_flag())
+        };
+        let value = match args.peek() {
+            Some(next) if !next.starts_with("--") => args.next().unwrap().clone(),
+            _ if is_flag() => "true".to_owned(),
+            _ => return Err(eyre!("Missing value for {arg}\n{USAGE}")),
+        };
+        match arg.as_str() {
+            "--part" => {
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(eyre!("Part must be 1 or 2, not {value}")),
+                }
+            }
+            "--input" => {
+                input = Some(match value.as_str() {
+                    "demo" => InputSource::Demo,
+                    "input" => InputSource::Input,
+                    path => InputSource::Path(PathBuf::from(path)),
+                })
+            }
+            "--format" => format = Format::parse(&value)?,
+            "--repeat" if is_bench => {
+                bench.repeat = value
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| eyre!("Repeat must be a positive number, not {value}"))?
+            }
+            "--report" if is_bench => {
+                bench.report = match value.as_str() {
+                    "markdown" => ReportFormat::Markdown,
+                    "csv" => ReportFormat::Csv,
+                    _ => return Err(eyre!("Report must be markdown or csv, not {value}")),
+                }
+            }
+            "--output" if is_bench => bench.output = Some(PathBuf::from(value)),
+            "--baseline" if is_bench => bench.baseline = Some(PathBuf::from(value)),
+            param => match param.strip_prefix("--") {
+                Some(name) => params.push((name.to_owned(), value)),
+                None => return Err(eyre!("Unexpected argument: {arg}\n{USAGE}")),
+            },
+        }
+    }
+    if !params.is_empty() {
+        match (&command, &selection) {
+            (Command::Run, Selection::Day(_)) => {}
+            (Command::Run, Selection::All) => {
+                return Err(eyre!("Parameters can only be used with a single day"))
+            }
+            (Command::Verify | Command::Bench, _) => {
+                return Err(eyre!(
+                    "Verify and bench take their parameters from each day's answers.toml"
+                ))
+            }
+        }
+    }
+    match (&command, &selection, &input) {
+        (Command::Run, Selection::All, Some(InputSource::Path(_))) => {
+            return Err(eyre!("An input path can only be used with a single day"))
+        }
+        (Command::Verify | Command::Bench, _, Some(InputSource::Path(_))) => {
+            return Err(eyre!("Only demo and input can be verified or benchmarked"))
+        }
+        _ => {}
+    }
+    Ok(Options {
+        command,
+        selection,
+        part,
+        input,
+        format,
+        params,
+        bench,
+    })
+}
+
+fn day_dir(day: usize) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{day}"))
+}
+
+fn input_path(day: usize, input: &InputSource) -> PathBuf {
+    let day_dir = day_dir(day);
+    match input {
+        InputSource::Demo => day_dir.join("demo.txt"),
+        InputSource::Input => day_dir.join("input.txt"),
+        InputSource::Path(path) => path.clone(),
+    }
+}
+
+fn summary(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Answer(answer, _) if answer.contains('\n') => "(see above)".to_owned(),
+        Outcome::Answer(answer, _) => answer.clone(),
+        Outcome::NotImplemented => "-".to_owned(),
+        Outcome::Failed(_) => "error".to_owned(),
+    }
+}
+
+fn input_label(source: &InputSource) -> String {
+    match source {
+        InputSource::Demo => "demo".to_owned(),
+        InputSource::Input => "input".to_owned(),
+        InputSource::Path(path) => path.display().to_string(),
+    }
+}
+
+fn run_part(context: &RecordContext, part: u8, parsed: &dyn Parsed, format: Format) -> Outcome {
+    let day = context.day;
+    let (outcome, diagnostics) = match format {
+        Format::Text => (solve_part(part, parsed), Vec::new()),
+        Format::Json => diagnostics::capture(|| solve_part(part, parsed)),
+    };
+    match &outcome {
+        Outcome::Answer(answer, duration) if answer.contains('\n') => {
+            format.say(&format!("Day {day} part {part} ({duration:?}):\n{answer}"))
+        }
+        Outcome::Answer(answer, duration) => {
+            format.say(&format!("Day {day} part {part}: {answer} ({duration:?})"))
+        }
+        Outcome::NotImplemented => format.say(&format!("Day {day} part {part} is not implemented")),
+        Outcome::Failed(e) => format.say(&format!("Day {day} part {part} failed: {e}")),
+    }
+    if format == Format::Json {
+        println!("{}", json_record(context, part, &outcome, diagnostics));
+    }
+    outcome
+}
+
+struct DayResult {
+    parse_time: Duration,
+    outcomes: Vec<(u8, Outcome)>,
+}
+
+fn run_day(
+    day: usize,
+    parts: &[u8],
+    source: &InputSource,
+    params: &[(String, String)],
+    format: Format,
+) -> eyre::Result<DayResult> {
+    let input = input_label(source);
+    let mut context = RecordContext {
+        day,
+        input: &input,
+        parse_time: None,
+        parse_diagnostics: &[],
+    };
+    let Some(entry) = &DAYS[day - 1] else {
+        format.say(&format!("Day {day} is not implemented"));
+        let outcomes: Vec<_> = parts
+            .iter()
+            .map(|p| (*p, Outcome::NotImplemented))
+            .collect();
+        if format == Format::Json {
+            for (part, outcome) in &outcomes {
+                println!("{}", json_record(&context, *part, outcome, Vec::new()));
+            }
+        }
+        return Ok(DayResult {
+            parse_time: Duration::ZERO,
+            outcomes,
+        });
+    };
+    let path = input_path(day, source);
+    let mut params = params.to_vec();
+    if let InputSource::Demo = source {
+        if let Some(answers) = Answers::load(&day_dir(day).join("answers.toml"))? {
+            for (name, value) in answers.params("demo") {
+                if !params.iter().any(|(n, _)| n == name) {
+                    params.push((name.to_owned(), value.to_owned()));
+                }
+            }
+        }
+    }
+    let start = Instant::now();
+    let params = params.iter().map(|(n, v)| (n.as_str(), v.as_str()));
+    let (parsed, parse_diagnostics) = match format {
+        Format::Text => (parse_file(&path, entry, params), Vec::new()),
+        Format::Json => diagnostics::capture(|| parse_file(&path, entry, params)),
+    };
+    let parse_time = start.elapsed();
+    context.parse_diagnostics = &parse_diagnostics;
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            if format == Format::Json {
+                let failed = Outcome::Failed(eyre!("{e}"));
+                for part in parts {
+                    println!("{}", json_record(&context, *part, &failed, Vec::new()));
+                }
+            }
+            return Err(e);
+        }
+    };
+    context.parse_time = Some(parse_time);
+    Ok(DayResult {
+        parse_time,
+        outcomes: parts
+            .iter()
+            .map(|part| (*part, run_part(&context, *part, parsed.as_ref(), format)))
+            .collect(),
+    })
+}
+
+fn parse_file<'a>(
+    path: &Path,
+    entry: &days::Day,
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> eyre::Result<Box<dyn Parsed>> {
+    parse_input(entry, &read_input(path)?, params)
+}
+
+fn parse_input<'a>(
+    entry: &days::Day,
+    input: &str,
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> eyre::Result<Box<dyn Parsed>> {
+    let mut values = Params::new(entry.params);
+    for (name, value) in params {
+        values.set(name, value)?;
+    }
+    (entry.parse)(input, &values)
+}
+
+fn read_input(path: &Path) -> eyre::Result<String> {
+    fs::read_to_string(path).map_err(|e| eyre!("Failed to read {}: {e}", path.display()))
+}
+
+fn print_summary(results: &[(usize, DayResult)], format: Format) {
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(
+            |(
+                day,
+                DayResult {
+                    parse_time,
+                    outcomes,
+                },
+            )| {
+                let summary = |part| {
+                    outcomes
+                        .iter()
+                        .find(|(p, _)| *p == part)
+                        .map_or(String::new(), |(_, o)| summary(o))
+                };
+                let total =
+                    *parse_time + outcomes.iter().map(|(_, o)| o.duration()).sum::<Duration>();
+                vec![
+                    day.to_string(),
+                    summary(1),
+                    summary(2),
+                    format!("{total:?}"),
+                ]
+            },
+        )
+        .collect();
+    format.say("");
+    format.say(&report::markdown_table(
+        &["Day", "Part 1", "Part 2", "Time"],
+        &rows,
+    ));
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    mismatched: usize,
+    failed: usize,
+    missing: usize,
+}
+
+// Checks each recorded answer for a day, printing a line per part.
+fn verify_day(day: usize, parts: &[u8], only: Option<&str>, tally: &mut Tally) -> eyre::Result<()> {
+    let Some(answers) = Answers::load(&day_dir(day).join("answers.toml"))? else {
+        return Ok(());
+    };
+    for input in answers.inputs().filter(|i| only.is_none_or(|o| o == *i)) {
+        let expected: Vec<(u8, &str)> = parts
+            .iter()
+            .filter_map(|part| Some((*part, answers.expected(input, *part)?)))
+            .collect();
+        if expected.is_empty() {
+            continue;
+        }
+        let label = format!("Day {day} {input}");
+        let Some(entry) = &DAYS[day - 1] else {
+            println!("{label}: day is not available");
+            tally.missing += expected.len();
+            continue;
+        };
+        let path = day_dir(day).join(format!("{input}.txt"));
+        // Only the pass/fail lines matter here, so diagnostics are dropped.
+        let params = answers.params(input);
+        let (parsed, _) = diagnostics::capture(|| parse_file(&path, entry, params));
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                println!("{label}: FAILED: {e}");
+                tally.failed += expected.len();
+                continue;
+            }
+        };
+        for (part, expected) in expected {
+            let label = format!("{label} part {part}");
+            let (outcome, _) = diagnostics::capture(|| solve_part(part, parsed.as_ref()));
+            match outcome {
+                Outcome::Answer(answer, _) if answer.trim() == expected.trim() => {
+                    println!("{label}: pass");
+                    tally.passed += 1;
+                }
+                Outcome::Answer(answer, _) => {
+                    let separator = if answer.contains('\n') || expected.contains('\n') {
+                        "\n"
+                    } else {
+                        " "
+                    };
+                    println!("{label}: MISMATCH expected{separator}{expected}{separator}got{separator}{answer}");
+                    tally.mismatched += 1;
+                }
+                Outcome::NotImplemented => {
+                    println!("{label}: not implemented");
+                    tally.missing += 1;
+                }
+                Outcome::Failed(e) => {
+                    println!("{label}: FAILED: {e}");
+                    tally.failed += 1;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn verify(options: &Options, parts: &[u8]) -> eyre::Result<()> {
+    let only = match options.input {
+        Some(InputSource::Demo) => Some("demo"),
+        Some(InputSource::Input) => Some("input"),
+        _ => None,
+    };
+    let days = match options.selection {
+        Selection::Day(day) => day..=day,
+        Selection::All => 1..=DAYS.len(),
+    };
+    let mut tally = Tally::default();
+    for day in days {
+        verify_day(day, parts, only, &mut tally)?;
+    }
+    let Tally {
+        passed,
+        mismatched,
+        failed,
+        missing,
+    } = tally;
+    println!();
+    println!(
+        "{passed} passed, {mismatched} mismatched, {failed} failed, {missing} not implemented"
+    );
+    if mismatched + failed > 0 {
+        return Err(eyre!("Verification failed"));
+    }
+    Ok(())
+}
+
+fn main() -> eyre::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_args(&args)?;
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    match options.command {
+        Command::Verify => return verify(&options, &parts),
+        Command::Bench => return bench::bench(&options, &parts),
+        Command::Run => {}
+    }
+    let input = options.input.unwrap_or(InputSource::Input);
+    match options.selection {
+        Selection::Day(day) => {
+            for (_, outcome) in
+                run_day(day, &parts, &input, &options.params, options.format)?.outcomes
+            {
+                if let Outcome::Failed(e) = outcome {
+                    return Err(e);
+                }
+            }
+        }
+        Selection::All => {
+            let mut results = Vec::new();
+            for day in 1..=DAYS.len() {
+                match run_day(day, &parts, &input, &options.params, options.format) {
+                    Ok(result) => results.push((day, result)),
+                    Err(e) => options.format.say(&format!("Skipping day {day}: {e}")),
+                }
+            }
+            print_summary(&results, options.format);
+        }
+    }
+    Ok(())
+}
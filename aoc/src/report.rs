@@ -0,0 +1,115 @@
+// Renders rows of cells as a markdown table, padding each column to line up.
+pub fn markdown_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    let mut lines = Vec::new();
+    for row in [&header, &separator].into_iter().chain(rows.iter()) {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.join("\n")
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn csv(header: &[&str], rows: &[Vec<String>]) -> String {
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    let mut lines = Vec::new();
+    for row in [&header].into_iter().chain(rows.iter()) {
+        let fields: Vec<_> = row.iter().map(|f| csv_field(f)).collect();
+        lines.push(fields.join(","));
+    }
+    lines.join("\n")
+}
+
+// Reads back the simple CSV written above: one record per line, with quoted
+// fields only where needed. Quoted fields can run over several lines.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                fields.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut fields));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    fields.push(field);
+    records.push(fields);
+    // Blank lines, including a trailing newline, aren't records.
+    records.retain(|record| record.len() > 1 || !record[0].is_empty());
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|f| f.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn csv_round_trips() {
+        let body = rows(&[
+            &["1", "plain", "12.5"],
+            &["2", "with, comma", ""],
+            &["3", "say \"hi\"", "\""],
+            &["4", "two\nlines", ","],
+        ]);
+        let text = csv(&["day", "answer", "time"], &body);
+        let mut expected = rows(&[&["day", "answer", "time"]]);
+        expected.extend(body);
+        assert_eq!(parse_csv(&text), expected);
+        assert_eq!(parse_csv(&format!("{text}\n\n")), expected);
+    }
+
+    #[test]
+    fn csv_only_quotes_when_needed() {
+        let text = csv(&["a", "b"], &rows(&[&["x", "y,z"]]));
+        assert_eq!(text, "a,b\nx,\"y,z\"");
+        assert_eq!(
+            parse_csv("a,b\r\nx,y\r\n"),
+            rows(&[&["a", "b"], &["x", "y"]])
+        );
+    }
+
+    #[test]
+    fn markdown_columns_line_up() {
+        let table = markdown_table(&["Day", "Time"], &rows(&[&["1", "12ms"], &["25", "3s"]]));
+        assert_eq!(
+            table,
+            "| Day | Time |\n| --- | ---- |\n| 1   | 12ms |\n| 25  | 3s   |"
+        );
+    }
+}
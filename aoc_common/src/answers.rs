@@ -0,0 +1,212 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use eyre::eyre;
+
+use crate::Params;
+
+// The expected answers for one day, read from its answers.toml. Each table is
+// an input ("demo" or "input") with optional `part1` and `part2` keys:
+//
+//     [demo]
+//     part1 = 95437
+//     part2 = "some text"
+//
+// Parameters the input needs, such as day 15's row on the demo, can be given as
+// `params.<name> = <value>` in the same table.
+//
+// Only the small subset of TOML needed for that is supported: comments,
+// tables, integers, basic strings and ''' multi-line literal strings.
+#[derive(Default)]
+pub struct Answers {
+    inputs: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> eyre::Result<Option<Answers>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read {}: {e}", path.display()))?;
+        Answers::parse(&text)
+            .map(Some)
+            .map_err(|e| eyre!("{}: {e}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> eyre::Result<Answers> {
+        let mut answers = Answers::default();
+        let mut table = None;
+        let mut lines = text.lines().enumerate();
+        while let Some((index, line)) = lines.next() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = name.trim().to_owned();
+                answers.inputs.entry(name.clone()).or_default();
+                table = Some(name);
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| eyre!("Line {line_number}: expected `key = value`"))?;
+            let key = key.trim();
+            let value = value.trim();
+            let value = if let Some(rest) = value.strip_prefix("'''") {
+                // Multi-line literal string, running until the closing quotes.
+                // A newline straight after the opening quotes is dropped.
+                let mut rows = Vec::new();
+                if !rest.is_empty() {
+                    rows.push(rest);
+                }
+                loop {
+                    if let Some(last) = rows.last_mut() {
+                        if let Some(end) = last.strip_suffix("'''") {
+                            *last = end;
+                            break;
+                        }
+                    }
+                    let (_, next) = lines.next().ok_or_else(|| {
+                        eyre!("Line {line_number}: unterminated multi-line string")
+                    })?;
+                    rows.push(next);
+                }
+                rows.join("\n")
+            } else if let Some(rest) = value.strip_prefix('"') {
+                rest.strip_suffix('"')
+                    .ok_or_else(|| eyre!("Line {line_number}: unterminated string"))?
+                    .to_owned()
+            } else if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                value.to_owned()
+            } else {
+                return Err(eyre!("Line {line_number}: unsupported value: {value}"));
+            };
+            let table = table
+                .as_ref()
+                .ok_or_else(|| eyre!("Line {line_number}: `{key}` is outside of a table"))?;
+            answers
+                .inputs
+                .get_mut(table)
+                .unwrap()
+                .insert(key.to_owned(), value);
+        }
+        Ok(answers)
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = &str> {
+        self.inputs.keys().map(|k| k.as_str())
+    }
+
+    pub fn params(&self, input: &str) -> impl Iterator<Item = (&str, &str)> {
+        self.inputs.get(input).into_iter().flat_map(|table| {
+            table
+                .iter()
+                .filter_map(|(key, value)| Some((key.strip_prefix("params.")?, value.as_str())))
+        })
+    }
+
+    /// Sets the parameters `input` needs, leaving any already set alone.
+    pub fn fill_params(&self, input: &str, params: &mut Params) -> eyre::Result<()> {
+        for (name, value) in self.params(input) {
+            if !params.is_set(name) {
+                params.set(name, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn expected(&self, input: &str, part: u8) -> Option<&str> {
+        self.inputs
+            .get(input)?
+            .get(&format!("part{part}"))
+            .map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Writes a value the way it would appear in answers.toml.
+    fn toml_value(value: &str) -> String {
+        if value.contains('\n') {
+            format!("'''\n{value}'''")
+        } else if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') {
+            value.to_owned()
+        } else {
+            format!("\"{value}\"")
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let tables = [
+            (
+                "demo",
+                vec![
+                    ("part1", "95437"),
+                    ("part2", "#..#\n.##.\n"),
+                    ("params.row", "10"),
+                ],
+            ),
+            (
+                "input",
+                vec![
+                    ("part1", "-42"),
+                    ("part2", "some text"),
+                    ("params.max", "4000000"),
+                ],
+            ),
+        ];
+        let mut text = String::from("# Expected answers\n");
+        for (table, entries) in &tables {
+            text.push_str(&format!("\n[{table}]\n"));
+            for (key, value) in entries {
+                text.push_str(&format!("{key} = {}\n", toml_value(value)));
+            }
+        }
+        let answers = Answers::parse(&text).unwrap();
+        assert_eq!(answers.inputs().collect::<Vec<_>>(), ["demo", "input"]);
+        for (table, entries) in &tables {
+            for (key, value) in entries {
+                match key.strip_prefix("params.") {
+                    Some(name) => assert!(answers.params(table).any(|p| p == (name, *value))),
+                    None => {
+                        let part = key.strip_prefix("part").unwrap().parse().unwrap();
+                        assert_eq!(answers.expected(table, part), Some(*value));
+                    }
+                }
+            }
+            assert_eq!(answers.params(table).count(), 1);
+        }
+        assert_eq!(answers.expected("other", 1), None);
+    }
+
+    #[test]
+    fn multi_line_strings() {
+        let answers =
+            Answers::parse("[demo]\npart1 = '''one line'''\npart2 = '''first\n  second'''\n")
+                .unwrap();
+        assert_eq!(answers.expected("demo", 1), Some("one line"));
+        assert_eq!(answers.expected("demo", 2), Some("first\n  second"));
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_read() {
+        for (text, error) in [
+            ("part1 = 1", "Line 1: `part1` is outside of a table"),
+            ("[demo]\npart1", "Line 2: expected `key = value`"),
+            ("[demo]\npart1 = \"open", "Line 2: unterminated string"),
+            (
+                "[demo]\npart1 = '''\nmore",
+                "Line 2: unterminated multi-line string",
+            ),
+            ("[demo]\npart1 = 1.5", "Line 2: unsupported value: 1.5"),
+            ("[demo]\npart1 = true", "Line 2: unsupported value: true"),
+        ] {
+            assert_eq!(Answers::parse(text).err().unwrap().to_string(), error);
+        }
+    }
+}
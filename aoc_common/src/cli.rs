@@ -0,0 +1,204 @@
+use std::{env, fs, path::Path, path::PathBuf, process, time::Instant};
+
+use eyre::eyre;
+
+use crate::{
+    answers::Answers,
+    diagnostics,
+    params::{Param, Params},
+    parse, read_stdin,
+    record::{json_record, solve_part, Format, Outcome, RecordContext},
+    Parsed, Solver,
+};
+
+enum Source {
+    Stdin,
+    Demo,
+    File(PathBuf),
+}
+
+fn usage<S: Solver>(name: &str) -> String {
+    let mut usage = format!(
+        "Usage: {name} [<input>|--demo] [--format text|json]{}\n\n\
+         Reads the puzzle input from <input>, the day's demo.txt with --demo, or stdin.\n\
+         With --format json, each part is written out as a line of JSON.\n",
+        if S::PARAMS.is_empty() {
+            ""
+        } else {
+            " [options]"
+        }
+    );
+    if !S::PARAMS.is_empty() {
+        usage.push_str("\nOptions:\n");
+        let flag = |p: &Param| {
+            if p.is_flag() {
+                format!("--{} [true|false]", p.name)
+            } else {
+                format!("--{} <value>", p.name)
+            }
+        };
+        let width = S::PARAMS.iter().map(|p| flag(p).len()).max().unwrap_or(0);
+        for param in S::PARAMS {
+            usage.push_str(&format!(
+                "  {:width$}  {} (default {})\n",
+                flag(param),
+                param.help,
+                param.default
+            ));
+        }
+        if S::PARAMS.iter().any(Param::is_flag) {
+            usage.push_str("\nA switch given without a value is turned on.\n");
+        }
+    }
+    usage
+}
+
+/// Runs a day's own binary, handling its command line arguments. `day_dir` is
+/// where the day's demo.txt lives, and each label names the answer to a part,
+/// in order. With `--format json` each part is written out as a JSON record,
+/// the same as `aoc run` gives.
+pub fn run<S>(day_dir: &str, labels: &[&str]) -> eyre::Result<()>
+where
+    S: Solver + 'static,
+    S::Input: 'static,
+{
+    let name = Path::new(day_dir)
+        .file_name()
+        .map_or("day".into(), |n| n.to_string_lossy());
+    let day = name
+        .strip_prefix("day")
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| eyre!("{day_dir} isn't a day's directory"))?;
+    let (source, params, format) = parse_args::<S>()?;
+    let label = match &source {
+        Source::Stdin => "stdin".to_owned(),
+        Source::Demo => "demo".to_owned(),
+        Source::File(path) => path.display().to_string(),
+    };
+    let mut context = RecordContext {
+        day,
+        input: &label,
+        parse_time: None,
+        parse_diagnostics: &[],
+    };
+    let parts = (1..).take(labels.len());
+    let start = Instant::now();
+    let (parsed, parse_diagnostics) = match format {
+        Format::Text => (load::<S>(day_dir, source, params), Vec::new()),
+        Format::Json => diagnostics::capture(|| load::<S>(day_dir, source, params)),
+    };
+    let parse_time = start.elapsed();
+    context.parse_diagnostics = &parse_diagnostics;
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            if format == Format::Json {
+                let failed = Outcome::Failed(eyre!("{e}"));
+                for part in parts {
+                    println!("{}", json_record(&context, part, &failed, Vec::new()));
+                }
+            }
+            return Err(e);
+        }
+    };
+    context.parse_time = Some(parse_time);
+    let mut failure = None;
+    for (part, label) in parts.zip(labels) {
+        let (outcome, diagnostics) = match format {
+            Format::Text => (solve_part(part, parsed.as_ref()), Vec::new()),
+            Format::Json => diagnostics::capture(|| solve_part(part, parsed.as_ref())),
+        };
+        match &outcome {
+            Outcome::Answer(answer, _) if answer.contains('\n') => {
+                format.say(&format!("{label}:\n{answer}"))
+            }
+            Outcome::Answer(answer, _) => format.say(&format!("{label}: {answer}")),
+            Outcome::NotImplemented => format.say(&format!("{label}: not implemented")),
+            Outcome::Failed(_) => {}
+        }
+        if format == Format::Json {
+            println!("{}", json_record(&context, part, &outcome, diagnostics));
+        }
+        if let Outcome::Failed(e) = outcome {
+            failure.get_or_insert(e);
+        }
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+fn parse_args<S: Solver>() -> eyre::Result<(Source, Params, Format)> {
+    let mut args = env::args().peekable();
+    let name = args
+        .next()
+        .and_then(|a| Some(Path::new(&a).file_name()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "day".to_owned());
+    let mut source = Source::Stdin;
+    let mut params = Params::new(S::PARAMS);
+    let mut format = Format::Text;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", usage::<S>(&name));
+                process::exit(0);
+            }
+            "--demo" => {
+                if !matches!(source, Source::Stdin) {
+                    return Err(eyre!("Only one input can be given\n{}", usage::<S>(&name)));
+                }
+                source = Source::Demo
+            }
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| eyre!("Missing value for --format\n{}", usage::<S>(&name)))?;
+                format = Format::parse(&value).map_err(|e| eyre!("{e}\n{}", usage::<S>(&name)))?;
+            }
+            flag if flag.starts_with("--") => {
+                let param = &flag[2..];
+                let value = match args.peek().map(String::as_str) {
+                    Some("true" | "false") => args.next().unwrap(),
+                    _ if params.is_flag(param) => "true".to_owned(),
+                    _ => args
+                        .next()
+                        .ok_or_else(|| eyre!("Missing value for {flag}\n{}", usage::<S>(&name)))?,
+                };
+                params
+                    .set(param, &value)
+                    .map_err(|e| eyre!("{e}\n{}", usage::<S>(&name)))?;
+            }
+            path => {
+                if !matches!(source, Source::Stdin) {
+                    return Err(eyre!("Only one input can be given\n{}", usage::<S>(&name)));
+                }
+                source = Source::File(PathBuf::from(path));
+            }
+        }
+    }
+    Ok((source, params, format))
+}
+
+// Reads and parses the input.
+fn load<S>(day_dir: &str, source: Source, mut params: Params) -> eyre::Result<Box<dyn Parsed>>
+where
+    S: Solver + 'static,
+    S::Input: 'static,
+{
+    let input = match source {
+        Source::Stdin => read_stdin()?,
+        Source::Demo => {
+            // The demo can need different parameters from the real input,
+            // which are kept with its expected answers.
+            let day_dir = Path::new(day_dir);
+            if let Some(answers) = Answers::load(&day_dir.join("answers.toml"))? {
+                answers.fill_params("demo", &mut params)?;
+            }
+            read_file(&day_dir.join("demo.txt"))?
+        }
+        Source::File(path) => read_file(&path)?,
+    };
+    parse::<S>(&input, &params)
+}
+
+fn read_file(path: &Path) -> eyre::Result<String> {
+    fs::read_to_string(path).map_err(|e| eyre!("Failed to read {}: {e}", path.display()))
+}
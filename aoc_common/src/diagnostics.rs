@@ -0,0 +1,31 @@
+use std::cell::RefCell;
+
+// Diagnostics are progress and debugging messages from a solver that aren't
+// part of the answer. They go to stderr unless something is capturing them.
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Reports a diagnostic message, see [`diag!`](crate::diag).
+pub fn emit(message: String) {
+    CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(messages) => messages.push(message),
+        None => eprintln!("{message}"),
+    })
+}
+
+/// Runs `f`, collecting any diagnostics it reports instead of printing them.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let outer = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let result = f();
+    let messages = CAPTURED.with(|captured| captured.replace(outer));
+    (result, messages.unwrap_or_default())
+}
+
+/// Reports a diagnostic message using `format!` syntax.
+#[macro_export]
+macro_rules! diag {
+    ($($arg:tt)*) => {
+        $crate::diagnostics::emit(format!($($arg)*))
+    };
+}
@@ -0,0 +1,613 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::ParseError;
+
+// Positions in a dense grid are (x, y) with (0, 0) in the top left corner and y
+// increasing downwards. Sparse and growing grids use signed (x, y) positions.
+pub type Pos = (usize, usize);
+pub type SignedPos = (i64, i64);
+
+/// Up, right, down and left.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// All eight surrounding directions, clockwise from up.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a character map, one row per line. Every row must be the same width.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> eyre::Result<T>) -> eyre::Result<Grid<T>> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let row_width = line.chars().count();
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(ParseError::new(
+                        y + 1,
+                        w.min(row_width) + 1,
+                        line,
+                        format!("expected a row {w} wide, found one {row_width} wide"),
+                    )
+                    .into())
+                }
+                _ => {}
+            }
+            parse_row(y, line, line.chars(), &mut f, &mut cells)?;
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a character map whose rows may be ragged, padding short rows on
+    /// the right with `padding`.
+    pub fn parse_padded(
+        input: &str,
+        padding: char,
+        mut f: impl FnMut(char) -> eyre::Result<T>,
+    ) -> eyre::Result<Grid<T>> {
+        let width = input.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let row_width = line.chars().count();
+            let row = line.chars().chain((row_width..width).map(|_| padding));
+            parse_row(y, line, row, &mut f, &mut cells)?;
+            height += 1;
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): SignedPos) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Steps from `pos` by `delta`, or `None` if that leaves the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if x < self.width && y < self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |d| self.step(pos, *d))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |d| self.step(pos, *d))
+    }
+
+    /// Positions from `pos` heading in direction `delta` until the edge of the
+    /// grid, not including `pos` itself.
+    pub fn ray(&self, pos: Pos, delta: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        let mut current = Some(pos);
+        std::iter::from_fn(move || {
+            current = self.step(current?, delta);
+            current
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, mut f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, t)| f(t)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let rows: Vec<String> = self
+            .rows()
+            .map(|r| r.iter().map(&mut f).collect())
+            .collect();
+        rows.join("\n")
+    }
+}
+
+// Parses the cells of row `y`, reporting where any bad cell is.
+fn parse_row<T>(
+    y: usize,
+    line: &str,
+    row: impl Iterator<Item = char>,
+    f: &mut impl FnMut(char) -> eyre::Result<T>,
+    cells: &mut Vec<T>,
+) -> Result<(), ParseError> {
+    for (x, c) in row.enumerate() {
+        let cell = f(c).map_err(|e| ParseError::new(y + 1, x + 1, line, e.to_string()))?;
+        cells.push(cell);
+    }
+    Ok(())
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn signed_neighbours4((x, y): SignedPos) -> impl Iterator<Item = SignedPos> {
+    ORTHOGONAL
+        .iter()
+        .map(move |(dx, dy)| (x + *dx as i64, y + *dy as i64))
+}
+
+pub fn signed_neighbours8((x, y): SignedPos) -> impl Iterator<Item = SignedPos> {
+    ALL_DIRECTIONS
+        .iter()
+        .map(move |(dx, dy)| (x + *dx as i64, y + *dy as i64))
+}
+
+/// An unbounded grid which only stores the cells that have been set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<SignedPos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, pos: SignedPos, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: SignedPos) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn get(&self, pos: SignedPos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn contains(&self, pos: SignedPos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SignedPos, &T)> + '_ {
+        self.cells.iter().map(|(pos, t)| (*pos, t))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = SignedPos> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The inclusive (min, max) corners of the set cells, if there are any.
+    pub fn bounds(&self) -> Option<(SignedPos, SignedPos)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+        Some(positions.fold((first, first), |(min, max), (x, y)| {
+            (
+                (min.0.min(*x), min.1.min(*y)),
+                (max.0.max(*x), max.1.max(*y)),
+            )
+        }))
+    }
+
+    /// Renders the bounding rectangle of the set cells, using `empty` for the gaps.
+    pub fn render(&self, empty: char, mut f: impl FnMut(&T) -> char) -> String {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds() else {
+            return String::new();
+        };
+        let rows: Vec<String> = (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| self.get((x, y)).map_or(empty, &mut f))
+                    .collect()
+            })
+            .collect();
+        rows.join("\n")
+    }
+}
+
+impl<T> FromIterator<(SignedPos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (SignedPos, T)>>(iter: I) -> SparseGrid<T> {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// A dense grid that grows to fit whatever position is written to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrowingGrid<T> {
+    grid: Grid<T>,
+    origin: SignedPos,
+    fill: T,
+}
+
+impl<T: Clone> GrowingGrid<T> {
+    /// Creates an empty grid where every unwritten cell reads as `fill`.
+    pub fn new(fill: T) -> GrowingGrid<T> {
+        GrowingGrid {
+            grid: Grid::new(0, 0, fill.clone()),
+            origin: (0, 0),
+            fill,
+        }
+    }
+
+    fn local(&self, (x, y): SignedPos) -> Option<Pos> {
+        let local = (x - self.origin.0, y - self.origin.1);
+        if self.grid.contains(local) {
+            Some((local.0 as usize, local.1 as usize))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: SignedPos) -> &T {
+        match self.local(pos) {
+            Some(local) => &self.grid[local],
+            None => &self.fill,
+        }
+    }
+
+    pub fn set(&mut self, pos: SignedPos, value: T) {
+        if self.local(pos).is_none() {
+            self.grow_to(pos);
+        }
+        let local = self.local(pos).unwrap();
+        self.grid[local] = value;
+    }
+
+    // Grows to include `pos`, at least doubling in any direction that grows so
+    // that repeated writes along an edge don't copy the grid every time.
+    fn grow_to(&mut self, (x, y): SignedPos) {
+        let (old_width, old_height) = (self.grid.width() as i64, self.grid.height() as i64);
+        let (mut min_x, mut min_y) = self.origin;
+        let (mut max_x, mut max_y) = (min_x + old_width, min_y + old_height);
+        if old_width == 0 || old_height == 0 {
+            (min_x, min_y, max_x, max_y) = (x, y, x + 1, y + 1);
+        }
+        if x < min_x {
+            min_x = x.min(min_x - old_width);
+        }
+        if x >= max_x {
+            max_x = (x + 1).max(max_x + old_width);
+        }
+        if y < min_y {
+            min_y = y.min(min_y - old_height);
+        }
+        if y >= max_y {
+            max_y = (y + 1).max(max_y + old_height);
+        }
+        let old = std::mem::replace(&mut self.grid, Grid::new(0, 0, self.fill.clone()));
+        let old_origin = self.origin;
+        self.grid = Grid::from_fn(
+            (max_x - min_x) as usize,
+            (max_y - min_y) as usize,
+            |(lx, ly)| {
+                let old_x = lx as i64 + min_x - old_origin.0;
+                let old_y = ly as i64 + min_y - old_origin.1;
+                if old.contains((old_x, old_y)) {
+                    old[(old_x as usize, old_y as usize)].clone()
+                } else {
+                    self.fill.clone()
+                }
+            },
+        );
+        self.origin = (min_x, min_y);
+    }
+
+    /// The inclusive (min, max) corners of the area allocated so far.
+    pub fn bounds(&self) -> Option<(SignedPos, SignedPos)> {
+        if self.grid.width() == 0 || self.grid.height() == 0 {
+            return None;
+        }
+        let (x, y) = self.origin;
+        Some((
+            (x, y),
+            (
+                x + self.grid.width() as i64 - 1,
+                y + self.grid.height() as i64 - 1,
+            ),
+        ))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SignedPos, &T)> + '_ {
+        let (ox, oy) = self.origin;
+        self.grid
+            .iter()
+            .map(move |((x, y), t)| ((x as i64 + ox, y as i64 + oy), t))
+    }
+
+    pub fn render(&self, f: impl FnMut(&T) -> char) -> String {
+        self.grid.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abcd\nefgh\nijkl", Ok).unwrap()
+    }
+
+    #[test]
+    fn bounds_checks() {
+        let mut grid = letters();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.get((3, 2)), Some(&'l'));
+        assert_eq!(grid.get((4, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get_mut((4, 2)), None);
+        assert!(grid.contains((0, 0)) && grid.contains((3, 2)));
+        assert!(!grid.contains((-1, 0)) && !grid.contains((0, -1)) && !grid.contains((4, 0)));
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+        assert_eq!(grid.step((3, 2), (0, 1)), None);
+        assert_eq!(grid.step((3, 2), (-1, -1)), Some((2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(4, 0) is outside the grid")]
+    fn index_outside_panics() {
+        let _ = letters()[(4, 0)];
+    }
+
+    #[test]
+    fn neighbours_at_edges() {
+        let grid = letters();
+        let at = |pos| grid.neighbours4(pos).map(|p| grid[p]).collect::<String>();
+        assert_eq!(at((0, 0)), "be");
+        assert_eq!(at((3, 2)), "hk");
+        assert_eq!(at((1, 1)), "bgje");
+        let around = |pos| grid.neighbours8(pos).map(|p| grid[p]).collect::<String>();
+        assert_eq!(around((0, 0)), "bfe");
+        assert_eq!(around((3, 0)), "hgc");
+        assert_eq!(around((1, 1)), "bcgkjiea");
+    }
+
+    #[test]
+    fn ray_stops_at_edge() {
+        let grid = letters();
+        let ray = |pos, delta| grid.ray(pos, delta).map(|p| grid[p]).collect::<String>();
+        assert_eq!(ray((1, 1), (1, 0)), "gh");
+        assert_eq!(ray((1, 1), (0, -1)), "b");
+        assert_eq!(ray((0, 0), (1, 1)), "fk");
+        assert_eq!(ray((3, 1), (1, 0)), "");
+        assert_eq!(ray((0, 2), (0, 1)), "");
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = letters();
+        assert_eq!(grid.row(1), ['e', 'f', 'g', 'h']);
+        assert_eq!(grid.column(2).collect::<String>(), "cgk");
+        assert_eq!(grid.column(0).rev().collect::<String>(), "iea");
+        assert_eq!(grid.column(3).collect::<String>(), "dhl");
+        // Past the last column would otherwise run on into the next row.
+        assert!(std::panic::catch_unwind(|| grid.column(4).count()).is_err());
+        assert_eq!(grid.find(|c| *c == 'j'), Some((1, 2)));
+        assert_eq!(grid.to_string(), "abcd\nefgh\nijkl");
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = Grid::parse("abc\nab\nabc", Ok).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let grid = Grid::parse_padded("ab\n\nabc\na", '.', Ok).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.render(|c| *c), "ab.\n...\nabc\na..");
+    }
+
+    #[test]
+    fn bad_cells_say_where() {
+        let parse = |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(eyre::eyre!("unexpected {c:?}")),
+        };
+        let error = Grid::parse_padded("#.\n.#x", '.', parse).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+        // Padding goes through the parser too.
+        let error = Grid::parse_padded("#.#\n.", ' ', parse).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn sparse_grid_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert((2, -1), 'a');
+        grid.insert((-1, 1), 'b');
+        assert_eq!(grid.bounds(), Some(((-1, -1), (2, 1))));
+        assert_eq!(grid.render('.', |c| *c), "...a\n....\nb...");
+        assert_eq!(grid.remove((2, -1)), Some('a'));
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn growing_grid_grows_towards_negatives() {
+        let mut grid = GrowingGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.get((-5, 7)), &'.');
+        grid.set((0, 0), 'a');
+        assert_eq!(grid.bounds(), Some(((0, 0), (0, 0))));
+        grid.set((-3, -2), 'b');
+        grid.set((-4, 0), 'c');
+        grid.set((1, -1), 'd');
+        let ((min_x, min_y), (max_x, max_y)) = grid.bounds().unwrap();
+        assert!(min_x <= -4 && min_y <= -2 && max_x >= 1 && max_y >= 0);
+        for (pos, value) in [
+            ((0, 0), 'a'),
+            ((-3, -2), 'b'),
+            ((-4, 0), 'c'),
+            ((1, -1), 'd'),
+        ] {
+            assert_eq!(grid.get(pos), &value);
+        }
+        assert_eq!(grid.get((-1, -1)), &'.');
+        assert_eq!(grid.iter().filter(|(_, c)| **c != '.').count(), 4);
+    }
+
+    #[test]
+    fn growing_grid_doubles() {
+        let mut grid = GrowingGrid::new(0);
+        for x in 0..100 {
+            grid.set((-x, 0), x);
+        }
+        let ((min_x, _), (max_x, _)) = grid.bounds().unwrap();
+        // Doubling leaves room to spare rather than fitting exactly.
+        assert!(min_x <= -99 && max_x >= 0);
+        assert!((max_x - min_x + 1) < 256);
+        assert_eq!((0..100).map(|x| grid.get((-x, 0))).sum::<i64>(), 4950);
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt::{self, Display, Write};
+
+/// Just enough JSON to write out machine-readable results.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // Whole numbers are kept apart so that big ones are written exactly.
+    Integer(i128),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object() -> Json {
+        Json::Object(Vec::new())
+    }
+
+    /// Adds a field to an object, for building one up in a chain.
+    pub fn with(mut self, key: &str, value: impl Into<Json>) -> Json {
+        if let Json::Object(fields) = &mut self {
+            fields.push((key.to_owned(), value.into()));
+        }
+        self
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Integer(n) => write!(f, "{n}"),
+            Json::Number(n) if n.is_finite() => write!(f, "{n}"),
+            Json::Number(_) => f.write_str("null"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Json {
+        Json::Bool(b)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Json {
+        Json::String(s.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Json {
+        Json::String(s)
+    }
+}
+
+macro_rules! integer_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Json {
+            fn from(n: $t) -> Json {
+                Json::Integer(n.into())
+            }
+        })*
+    };
+}
+
+integer_from!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Json {
+    fn from(n: usize) -> Json {
+        Json::Integer(n as i128)
+    }
+}
+
+impl From<isize> for Json {
+    fn from(n: isize) -> Json {
+        Json::Integer(n as i128)
+    }
+}
+
+impl From<u128> for Json {
+    fn from(n: u128) -> Json {
+        i128::try_from(n).map_or(Json::Number(n as f64), Json::Integer)
+    }
+}
+
+impl From<f32> for Json {
+    fn from(n: f32) -> Json {
+        Json::Number(n.into())
+    }
+}
+
+impl From<f64> for Json {
+    fn from(n: f64) -> Json {
+        Json::Number(n)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(items: Vec<T>) -> Json {
+        Json::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings() {
+        assert_eq!(Json::from("plain").to_string(), r#""plain""#);
+        assert_eq!(
+            Json::from("say \"hi\"\\now").to_string(),
+            r#""say \"hi\"\\now""#
+        );
+        assert_eq!(Json::from("a\nb\r\tc").to_string(), r#""a\nb\r\tc""#);
+        assert_eq!(Json::from("\u{0}\u{1b}").to_string(), r#""\u0000\u001b""#);
+        // Anything else is left to UTF-8.
+        assert_eq!(Json::from("é ☃ \u{7f}").to_string(), "\"é ☃ \u{7f}\"");
+        let object = Json::object().with("a\"b", "c");
+        assert_eq!(object.to_string(), r#"{"a\"b":"c"}"#);
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Json::from(0u8).to_string(), "0");
+        assert_eq!(Json::from(-42i64).to_string(), "-42");
+        assert_eq!(Json::from(1.5f64).to_string(), "1.5");
+        assert_eq!(Json::from(-0.25f32).to_string(), "-0.25");
+        // Whole numbers are written without a fraction or exponent.
+        assert_eq!(Json::from(1e15).to_string(), "1000000000000000");
+        assert_eq!(Json::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(
+            Json::from(i128::MIN).to_string(),
+            "-170141183460469231731687303715884105728"
+        );
+        // Only the very biggest fall back to floating point.
+        assert_eq!(
+            Json::from(u128::MAX).to_string(),
+            format!("{}", u128::MAX as f64)
+        );
+        assert_eq!(Json::from(f64::NAN).to_string(), "null");
+        assert_eq!(Json::from(f64::INFINITY).to_string(), "null");
+    }
+
+    #[test]
+    fn nesting() {
+        let json = Json::object()
+            .with("list", vec![Some(1), None])
+            .with("flag", true)
+            .with("empty", Json::object())
+            .with("none", Json::Array(Vec::new()));
+        assert_eq!(
+            json.to_string(),
+            r#"{"list":[1,null],"flag":true,"empty":{},"none":[]}"#
+        );
+        // Fields can only be added to objects.
+        assert_eq!(Json::Null.with("a", 1), Json::Null);
+    }
+}
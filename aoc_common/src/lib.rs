@@ -0,0 +1,14 @@
+pub mod answers;
+pub mod cli;
+pub mod diagnostics;
+pub mod grid;
+pub mod json;
+mod params;
+pub mod parser;
+pub mod record;
+mod solver;
+
+pub use grid::{Grid, GrowingGrid, SparseGrid};
+pub use params::{Param, Params};
+pub use parser::ParseError;
+pub use solver::{parse, read_stdin, NotImplemented, Parsed, Solver, Unsolved};
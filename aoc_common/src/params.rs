@@ -0,0 +1,76 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use eyre::eyre;
+
+/// A named value that a day would otherwise have baked in, such as the row to
+/// scan or a time limit.
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+impl Param {
+    /// Whether this switches something on or off, so can be given as a bare
+    /// flag to turn it on.
+    pub fn is_flag(&self) -> bool {
+        matches!(self.default, "true" | "false")
+    }
+}
+
+/// The values for a day's parameters, falling back to their defaults.
+#[derive(Clone, Debug)]
+pub struct Params {
+    declared: &'static [Param],
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new(declared: &'static [Param]) -> Params {
+        Params {
+            declared,
+            values: BTreeMap::new(),
+        }
+    }
+
+    pub fn declared(&self) -> &'static [Param] {
+        self.declared
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) -> eyre::Result<()> {
+        if !self.declared.iter().any(|p| p.name == name) {
+            return Err(eyre!("Unknown parameter: {name}"));
+        }
+        self.values.insert(name.to_owned(), value.to_owned());
+        Ok(())
+    }
+
+    pub fn is_flag(&self, name: &str) -> bool {
+        self.declared.iter().any(|p| p.name == name && p.is_flag())
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    pub fn get<T>(&self, name: &str) -> eyre::Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = match self.values.get(name) {
+            Some(value) => value.as_str(),
+            None => {
+                self.declared
+                    .iter()
+                    .find(|p| p.name == name)
+                    .ok_or_else(|| eyre!("Unknown parameter: {name}"))?
+                    .default
+            }
+        };
+        value
+            .parse()
+            .map_err(|e| eyre!("Invalid value for {name}: {value}: {e}"))
+    }
+}
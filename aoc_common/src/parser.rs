@@ -0,0 +1,310 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// An error in a day's input, pointing at where it went wrong.
+///
+/// Displays as the message followed by the offending line with a caret under
+/// the column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, source_line: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+            source_line: source_line.to_owned(),
+        }
+    }
+
+    /// The input stopped at `line` when `expected` was still to come.
+    pub fn end_of_input(line: usize, expected: &str) -> Self {
+        ParseError::new(
+            line,
+            1,
+            "",
+            format!("expected {expected}, found end of input"),
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", self.source_line)?;
+        write!(f, "    {}^", " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A cursor over one line of input, for pulling tokens out of it in order.
+#[derive(Clone, Debug)]
+pub struct Line<'a> {
+    text: &'a str,
+    number: usize,
+    // Byte offset of the cursor.
+    pos: usize,
+}
+
+/// The lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line::new(idx + 1, text))
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Line<'a> {
+        Line {
+            text,
+            number,
+            pos: 0,
+        }
+    }
+
+    /// The 1-based line number.
+    pub fn line_number(&self) -> usize {
+        self.number
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The text left after the cursor.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// An error at the cursor.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column(), self.text, message)
+    }
+
+    /// An error saying what should have been at the cursor, and what's there instead.
+    pub fn expected(&self, expected: &str) -> ParseError {
+        // Show the next token, along with any space in front of it.
+        let rest = self.rest();
+        let token = rest.trim_start();
+        let len = match token.find(|c: char| c.is_whitespace() || c == ',') {
+            Some(0) => token.chars().next().map_or(0, char::len_utf8),
+            Some(len) => len,
+            None => token.len(),
+        };
+        let len = rest.len() - token.len() + len;
+        let found = match &rest[..len] {
+            "" => "end of line".to_owned(),
+            token => format!("{token:?}"),
+        };
+        self.error(format!("expected {expected}, found {found}"))
+    }
+
+    /// Consumes `tag`, which must come next.
+    pub fn tag(&mut self, tag: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(tag) {
+            self.pos += tag.len();
+            Ok(())
+        } else {
+            Err(self.expected(&format!("{tag:?}")))
+        }
+    }
+
+    /// Consumes `tag` if it comes next, returning whether it did.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        self.tag(tag).is_ok()
+    }
+
+    /// Consumes characters while `f` holds, returning them.
+    pub fn take_while(&mut self, mut f: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes everything up to `delimiter`, which is consumed too.
+    pub fn until(&mut self, delimiter: &str) -> Result<&'a str, ParseError> {
+        let rest = self.rest();
+        match rest.find(delimiter) {
+            Some(len) => {
+                self.pos += len + delimiter.len();
+                Ok(&rest[..len])
+            }
+            None => {
+                let mut end = self.clone();
+                end.pos = end.text.len();
+                Err(end.expected(&format!("{delimiter:?}")))
+            }
+        }
+    }
+
+    /// Consumes the rest of the line.
+    pub fn take_rest(&mut self) -> &'a str {
+        let rest = self.rest();
+        self.pos = self.text.len();
+        rest
+    }
+
+    /// Consumes a run of letters, digits and underscores.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        let start = self.clone();
+        match self.take_while(|c| c.is_alphanumeric() || c == '_') {
+            "" => Err(start.expected("a word")),
+            word => Ok(word),
+        }
+    }
+
+    /// Consumes an optionally signed integer.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.clone();
+        let sign = usize::from(self.rest().starts_with(['-', '+']));
+        let digits = self.rest()[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len() - sign);
+        if digits == 0 {
+            return Err(start.expected("a number"));
+        }
+        let text = &self.rest()[..sign + digits];
+        let value = text
+            .parse()
+            .map_err(|_| start.error(format!("{text} is out of range")))?;
+        self.pos += sign + digits;
+        Ok(value)
+    }
+
+    /// Consumes whichever of `options` comes next, returning its value.
+    pub fn one_of<T: Clone>(&mut self, options: &[(&str, T)]) -> Result<T, ParseError> {
+        for (tag, value) in options {
+            if self.eat(tag) {
+                return Ok(value.clone());
+            }
+        }
+        let names: Vec<String> = options.iter().map(|(t, _)| format!("{t:?}")).collect();
+        Err(self.expected(&format!("one of {}", names.join(", "))))
+    }
+
+    /// Checks nothing is left on the line.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.expected("end of line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_numbers() {
+        let mut line = Line::new(1, "-12 +7 3");
+        assert_eq!(line.number::<i32>(), Ok(-12));
+        line.tag(" ").unwrap();
+        assert_eq!(line.number::<i32>(), Ok(7));
+        line.tag(" ").unwrap();
+        assert_eq!(line.number::<u8>(), Ok(3));
+        assert!(line.is_empty());
+    }
+
+    #[test]
+    fn numbers_out_of_range() {
+        let mut line = Line::new(2, "x 300");
+        line.tag("x ").unwrap();
+        let err = line.number::<u8>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "300 is out of range");
+        // Nothing is consumed on failure.
+        assert_eq!(line.rest(), "300");
+
+        let err = Line::new(1, "-5").number::<u32>().unwrap_err();
+        assert_eq!(err.message, "-5 is out of range");
+    }
+
+    #[test]
+    fn not_numbers() {
+        let err = Line::new(1, "-").number::<i32>().unwrap_err();
+        assert_eq!(err.message, r#"expected a number, found "-""#);
+        let err = Line::new(1, "abc def").number::<i32>().unwrap_err();
+        assert_eq!(err.message, r#"expected a number, found "abc""#);
+    }
+
+    #[test]
+    fn expected_shows_the_next_token() {
+        let mut line = Line::new(1, "move 1 from 2");
+        line.tag("move").unwrap();
+        let err = line.word().unwrap_err();
+        assert_eq!(err.column, 5);
+        assert_eq!(err.message, r#"expected a word, found " 1""#);
+
+        let mut line = Line::new(1, "1,2");
+        line.number::<i32>().unwrap();
+        let err = line.end().unwrap_err();
+        assert_eq!(err.column, 2);
+        assert_eq!(err.message, r#"expected end of line, found ",""#);
+
+        let mut line = Line::new(1, "a");
+        line.word().unwrap();
+        let err = line.tag(" -> ").unwrap_err();
+        assert_eq!(err.column, 2);
+        assert_eq!(err.message, r#"expected " -> ", found end of line"#);
+    }
+
+    #[test]
+    fn caret_under_the_column() {
+        let mut line = Line::new(3, "12 x");
+        line.number::<i32>().unwrap();
+        let err = line.end().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: expected end of line, found \" x\"\n    12 x\n      ^"
+        );
+        assert_eq!(
+            ParseError::end_of_input(4, "a number").to_string(),
+            "line 4, column 1: expected a number, found end of input\n    \n    ^"
+        );
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let mut line = Line::new(1, "naïve x");
+        assert_eq!(line.word(), Ok("naïve"));
+        let err = line.end().unwrap_err();
+        assert_eq!(err.column, 6);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: expected end of line, found \" x\"\n    naïve x\n         ^"
+        );
+
+        let mut line = Line::new(1, "→ ü");
+        line.tag("→ ").unwrap();
+        let err = line.number::<i32>().unwrap_err();
+        assert_eq!(err.column, 3);
+        assert_eq!(err.message, r#"expected a number, found "ü""#);
+    }
+}
@@ -0,0 +1,137 @@
+//! Running a day's parts and writing out how they went, the same way for the
+//! `aoc` runner and each day's own binary.
+
+use std::time::{Duration, Instant};
+
+use eyre::eyre;
+
+use crate::{json::Json, NotImplemented, Parsed};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    // One JSON record per line on stdout for each part run, with everything
+    // else on stderr.
+    Json,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> eyre::Result<Format> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(eyre!("Format must be text or json, not {value}")),
+        }
+    }
+
+    /// Prints human-readable progress, which keeps out of the way of JSON
+    /// records on stdout.
+    pub fn say(self, message: &str) {
+        match self {
+            Format::Text => println!("{message}"),
+            Format::Json => eprintln!("{message}"),
+        }
+    }
+}
+
+pub enum Outcome {
+    Answer(String, Duration),
+    NotImplemented,
+    Failed(eyre::Report),
+}
+
+impl Outcome {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Outcome::Answer(_, duration) => *duration,
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+pub fn solve_part(part: u8, parsed: &dyn Parsed) -> Outcome {
+    let start = Instant::now();
+    let result = match part {
+        1 => parsed.part1(),
+        _ => parsed.part2(),
+    };
+    let duration = start.elapsed();
+    match result {
+        Ok(answer) => Outcome::Answer(answer, duration),
+        Err(e) if e.is::<NotImplemented>() => Outcome::NotImplemented,
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+/// What the records for each part of a day's run share.
+pub struct RecordContext<'a> {
+    pub day: usize,
+    /// "demo", "input" or the path read from.
+    pub input: &'a str,
+    pub parse_time: Option<Duration>,
+    pub parse_diagnostics: &'a [String],
+}
+
+/// The JSON record for running one part.
+pub fn json_record(
+    context: &RecordContext,
+    part: u8,
+    outcome: &Outcome,
+    diagnostics: Vec<String>,
+) -> Json {
+    let (status, answer, error) = match outcome {
+        Outcome::Answer(answer, _) => ("ok", Some(answer.clone()), None),
+        Outcome::NotImplemented => ("not_implemented", None, None),
+        Outcome::Failed(e) => ("error", None, Some(e.to_string())),
+    };
+    let solve_time = match outcome {
+        Outcome::Answer(_, duration) => Some(duration.as_nanos()),
+        _ => None,
+    };
+    let diagnostics: Vec<String> = context
+        .parse_diagnostics
+        .iter()
+        .cloned()
+        .chain(diagnostics)
+        .collect();
+    Json::object()
+        .with("day", context.day)
+        .with("part", part)
+        .with("input", context.input)
+        .with("status", status)
+        .with("answer", answer)
+        .with("error", error)
+        .with("parse_ns", context.parse_time.map(|t| t.as_nanos()))
+        .with("solve_ns", solve_time)
+        .with("diagnostics", diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let context = RecordContext {
+            day: 3,
+            input: "demo",
+            parse_time: Some(Duration::from_nanos(12)),
+            parse_diagnostics: &["parsed".to_owned()],
+        };
+        let answer = Outcome::Answer("157".to_owned(), Duration::from_nanos(34));
+        assert_eq!(
+            json_record(&context, 1, &answer, vec!["solved".to_owned()]).to_string(),
+            r#"{"day":3,"part":1,"input":"demo","status":"ok","answer":"157","error":null,"parse_ns":12,"solve_ns":34,"diagnostics":["parsed","solved"]}"#
+        );
+        let failed = Outcome::Failed(eyre!("No badge"));
+        assert_eq!(
+            json_record(&context, 2, &failed, Vec::new()).to_string(),
+            r#"{"day":3,"part":2,"input":"demo","status":"error","answer":null,"error":"No badge","parse_ns":12,"solve_ns":null,"diagnostics":["parsed"]}"#
+        );
+        assert!(
+            json_record(&context, 2, &Outcome::NotImplemented, Vec::new())
+                .to_string()
+                .contains(r#""status":"not_implemented","answer":null"#)
+        );
+    }
+}
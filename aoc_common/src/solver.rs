@@ -0,0 +1,85 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, Read},
+};
+
+use crate::params::{Param, Params};
+
+/// A solution to a single day's puzzle.
+///
+/// The input is parsed once into a typed model which both parts then work from.
+pub trait Solver {
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Named values the parsed input carries, which can be overridden from the
+    /// command line.
+    const PARAMS: &'static [Param] = &[];
+
+    /// Parses with the given parameter values. Days without parameters can
+    /// ignore them.
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input>;
+
+    /// Parses with every parameter at its default.
+    fn parse(input: &str) -> eyre::Result<Self::Input> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
+    }
+
+    fn part1(input: &Self::Input) -> eyre::Result<Self::Part1>;
+    fn part2(input: &Self::Input) -> eyre::Result<Self::Part2>;
+}
+
+/// Answer type for a part that hasn't been solved yet.
+pub enum Unsolved {}
+
+impl Display for Unsolved {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Error returned by a part that hasn't been solved yet.
+#[derive(Debug)]
+pub struct NotImplemented;
+
+impl Display for NotImplemented {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not implemented")
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// Parsed input for some day, with the answers rendered as strings so that
+/// every day can be driven the same way.
+pub trait Parsed {
+    fn part1(&self) -> eyre::Result<String>;
+    fn part2(&self) -> eyre::Result<String>;
+}
+
+struct ParsedInput<S: Solver>(S::Input);
+
+impl<S: Solver> Parsed for ParsedInput<S> {
+    fn part1(&self) -> eyre::Result<String> {
+        Ok(S::part1(&self.0)?.to_string())
+    }
+
+    fn part2(&self) -> eyre::Result<String> {
+        Ok(S::part2(&self.0)?.to_string())
+    }
+}
+
+pub fn parse<S>(input: &str, params: &Params) -> eyre::Result<Box<dyn Parsed>>
+where
+    S: Solver + 'static,
+    S::Input: 'static,
+{
+    Ok(Box::new(ParsedInput::<S>(S::parse_with(input, params)?)))
+}
+
+pub fn read_stdin() -> eyre::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
@@ -0,0 +1,44 @@
+use std::vec::Vec;
+
+use aoc_common::{parser::lines, Params, Solver};
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    // Total calories carried by each elf, smallest first.
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        let mut elves = Vec::new();
+        let mut current_elf = 0;
+        for mut line in lines(input) {
+            if line.is_empty() {
+                elves.push(current_elf);
+                current_elf = 0;
+            } else {
+                current_elf += line.number::<i32>()?;
+                line.end()?;
+            }
+        }
+        elves.push(current_elf);
+        elves.sort();
+        Ok(elves)
+    }
+
+    fn part1(elves: &Self::Input) -> eyre::Result<i32> {
+        elves
+            .iter()
+            .max()
+            .copied()
+            .ok_or_else(|| eyre::eyre!("No elves"))
+    }
+
+    fn part2(elves: &Self::Input) -> eyre::Result<i32> {
+        if elves.len() < 3 {
+            return Err(eyre::eyre!("Fewer than 3 elves"));
+        }
+        Ok(elves[elves.len() - 3..elves.len()].iter().sum::<i32>())
+    }
+}
@@ -0,0 +1,80 @@
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+fn line_to_instruction(mut line: Line) -> Result<Instruction, ParseError> {
+    let instruction = match line.one_of(&[("noop", false), ("addx ", true)])? {
+        false => Instruction::Noop,
+        true => Instruction::AddX(line.number()?),
+    };
+    line.end()?;
+    Ok(instruction)
+}
+
+/// Returns the value of the X register during each cycle, starting from cycle 1.
+fn register_values(program: &[Instruction]) -> Vec<i32> {
+    let mut x = 1;
+    let mut values = vec![x];
+    for instruction in program {
+        match instruction {
+            Instruction::Noop => {
+                values.push(x);
+            }
+            Instruction::AddX(count) => {
+                values.push(x);
+                x += count;
+                values.push(x);
+            }
+        }
+    }
+    values
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Ok(lines(input)
+            .map(line_to_instruction)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(program: &Self::Input) -> eyre::Result<i32> {
+        let values = register_values(program);
+        Ok([20, 60, 100, 140, 180, 220]
+            .iter()
+            .filter_map(|cycle| values.get(*cycle as usize - 1).map(|x| cycle * x))
+            .sum())
+    }
+
+    fn part2(program: &Self::Input) -> eyre::Result<String> {
+        let values = register_values(program);
+        let rows: Vec<String> = values
+            .chunks(40)
+            .take(6)
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(offset, x)| {
+                        if x - 1 <= offset as i32 && x + 1 >= offset as i32 {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(rows.join("\n"))
+    }
+}
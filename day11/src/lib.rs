@@ -0,0 +1,239 @@
+use std::collections::VecDeque;
+
+use aoc_common::{
+    diag,
+    parser::{lines, Line},
+    Param, Params, ParseError, Solver,
+};
+
+#[derive(Debug, Clone)]
+enum MonkeyOperation {
+    Multiply(u64),
+    Add(u64),
+    Square,
+}
+
+impl MonkeyOperation {
+    fn apply(&self, value: u64) -> u64 {
+        match self {
+            MonkeyOperation::Add(a) => value + a,
+            MonkeyOperation::Multiply(m) => value * m,
+            MonkeyOperation::Square => value * value,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: VecDeque<u64>,
+    operation: MonkeyOperation,
+    modulus: u64,
+    if_true: usize,
+    if_false: usize,
+    total_item_count: u64,
+}
+
+// Takes the line after `previous`, which is expected to hold `what`.
+fn next_line<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    previous: &Line,
+    what: &str,
+) -> Result<Line<'a>, ParseError> {
+    lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(previous.line_number() + 1, what))
+}
+
+// Reads which monkey to throw to, along with where it was given.
+fn throw_target<'a>(mut line: Line<'a>, outcome: &str) -> Result<(usize, Line<'a>), ParseError> {
+    line.tag(&format!("    If {outcome}: throw to monkey "))?;
+    let at = line.clone();
+    let target = line.number()?;
+    line.end()?;
+    Ok((target, at))
+}
+
+// Reads the monkey starting at `header`. The monkeys it throws to are returned
+// with where they were given, so they can be checked once every monkey is known.
+fn lines_to_monkey<'a>(
+    mut header: Line<'a>,
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    idx: usize,
+) -> Result<(Monkey, [(usize, Line<'a>); 2]), ParseError> {
+    header.tag("Monkey ")?;
+    let start = header.clone();
+    if header.number::<usize>()? != idx {
+        return Err(start.expected(&format!("monkey {idx}")));
+    }
+    header.tag(":")?;
+    header.end()?;
+
+    let mut line = next_line(lines, &header, "starting items")?;
+    line.tag("  Starting items: ")?;
+    let mut items = VecDeque::new();
+    while !line.is_empty() {
+        if !items.is_empty() {
+            line.tag(", ")?;
+        }
+        items.push_back(line.number()?);
+    }
+
+    let mut line = next_line(lines, &line, "an operation")?;
+    line.tag("  Operation: new = old ")?;
+    let operation = if line.eat("* old") {
+        MonkeyOperation::Square
+    } else if line.eat("+ ") {
+        MonkeyOperation::Add(line.number()?)
+    } else if line.eat("* ") {
+        MonkeyOperation::Multiply(line.number()?)
+    } else {
+        return Err(line.expected("\"+ <n>\", \"* <n>\" or \"* old\""));
+    };
+    line.end()?;
+
+    let mut line = next_line(lines, &line, "a test")?;
+    line.tag("  Test: divisible by ")?;
+    let start = line.clone();
+    let modulus = line.number()?;
+    if modulus == 0 {
+        return Err(start.error("can't test for divisibility by 0"));
+    }
+    line.end()?;
+
+    let if_true = throw_target(next_line(lines, &line, "an if true branch")?, "true")?;
+    let if_false = throw_target(next_line(lines, &if_true.1, "an if false branch")?, "false")?;
+    let monkey = Monkey {
+        items,
+        operation,
+        modulus,
+        if_true: if_true.0,
+        if_false: if_false.0,
+        total_item_count: 0,
+    };
+    Ok((monkey, [if_true, if_false]))
+}
+
+#[derive(Clone, Copy)]
+enum Worry {
+    // Worry levels are divided by three after each inspection.
+    Relieved,
+    // Worry levels are left alone, so they are kept small by working modulo all
+    // the monkeys' tests.
+    Unrelieved,
+}
+
+fn monkey_business(monkeys: &[Monkey], rounds: usize, worry: Worry) -> eyre::Result<u64> {
+    if monkeys.len() < 2 {
+        return Err(eyre::eyre!("Need at least two monkeys"));
+    }
+    let mut monkeys = monkeys.to_vec();
+    let total_mod: u64 = monkeys.iter().map(|m| m.modulus).product();
+    for _ in 0..rounds {
+        for idx in 0..monkeys.len() {
+            while !monkeys[idx].items.is_empty() {
+                monkeys[idx].total_item_count += 1;
+                let item = monkeys[idx].items.pop_front().unwrap();
+                let item = match worry {
+                    Worry::Relieved => monkeys[idx].operation.apply(item) / 3,
+                    Worry::Unrelieved => monkeys[idx].operation.apply(item) % total_mod,
+                };
+                let next_idx = if item.is_multiple_of(monkeys[idx].modulus) {
+                    monkeys[idx].if_true
+                } else {
+                    monkeys[idx].if_false
+                };
+                monkeys[next_idx].items.push_back(item);
+            }
+        }
+    }
+    let mut total_item_counts: Vec<_> = monkeys.iter().map(|m| m.total_item_count).collect();
+    total_item_counts.sort();
+    total_item_counts.reverse();
+    diag!(
+        "Top two monkeys {} and {}",
+        total_item_counts[0],
+        total_item_counts[1]
+    );
+    Ok(total_item_counts[0] * total_item_counts[1])
+}
+
+pub struct Troop {
+    monkeys: Vec<Monkey>,
+    relieved_rounds: usize,
+    rounds: usize,
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Troop;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "relieved-rounds",
+            default: "20",
+            help: "Rounds to play in part 1, where worry is relieved",
+        },
+        Param {
+            name: "rounds",
+            default: "10000",
+            help: "Rounds to play in part 2",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input> {
+        let mut monkeys = Vec::<Monkey>::new();
+        let mut targets = Vec::new();
+        let mut lines = lines(input).filter(|line| !line.is_empty());
+        while let Some(header) = lines.next() {
+            let idx = monkeys.len();
+            let (monkey, throws) = lines_to_monkey(header, &mut lines, idx)?;
+            // Its items would never leave it, so the round would never end.
+            if let Some((_, at)) = throws.iter().find(|(target, _)| *target == idx) {
+                return Err(at
+                    .error(format!("monkey {idx} can't throw to itself"))
+                    .into());
+            }
+            monkeys.push(monkey);
+            targets.extend(throws);
+        }
+        for (target, at) in targets {
+            if target >= monkeys.len() {
+                return Err(at.error(format!("there is no monkey {target}")).into());
+            }
+        }
+        diag!("Read {} monkeys", monkeys.len());
+        Ok(Troop {
+            monkeys,
+            relieved_rounds: params.get("relieved-rounds")?,
+            rounds: params.get("rounds")?,
+        })
+    }
+
+    fn part1(troop: &Self::Input) -> eyre::Result<u64> {
+        monkey_business(&troop.monkeys, troop.relieved_rounds, Worry::Relieved)
+    }
+
+    fn part2(troop: &Self::Input) -> eyre::Result<u64> {
+        monkey_business(&troop.monkeys, troop.rounds, Worry::Unrelieved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throwing_to_itself() {
+        let input =
+            include_str!("../demo.txt").replacen("throw to monkey 2", "throw to monkey 0", 1);
+        let Err(err) = Day11::parse(&input) else {
+            panic!("parsed a monkey throwing to itself");
+        };
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (5, 30));
+        assert_eq!(err.message, "monkey 0 can't throw to itself");
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+use aoc_common::{Grid, Params, Solver};
+
+pub struct Challenge {
+    height_map: Grid<i32>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+fn parse_height_map(input: &str) -> eyre::Result<Challenge> {
+    let letters = Grid::parse(input, |c| match c {
+        'a'..='z' | 'S' | 'E' => Ok(c),
+        _ => Err(eyre::eyre!(
+            "expected a height from a-z, S or E, found {c:?}"
+        )),
+    })?;
+    let start = letters
+        .find(|c| *c == 'S')
+        .ok_or_else(|| eyre::eyre!("No start position"))?;
+    let end = letters
+        .find(|c| *c == 'E')
+        .ok_or_else(|| eyre::eyre!("No end position"))?;
+    let height_map = letters.map(|c| match c {
+        'S' => 'a' as i32,
+        'E' => 'z' as i32,
+        c => *c as i32,
+    });
+    Ok(Challenge {
+        height_map,
+        start,
+        end,
+    })
+}
+
+/// Works out the distance from every square to the end, or `None` if it can't be reached.
+fn distances_to_end(challenge: &Challenge) -> Grid<Option<usize>> {
+    let height_map = &challenge.height_map;
+    let mut distances = Grid::new(height_map.width(), height_map.height(), None);
+    distances[challenge.end] = Some(0);
+    let mut to_visit = VecDeque::from([challenge.end]);
+    while let Some(pos) = to_visit.pop_front() {
+        let d = distances[pos].unwrap();
+        let current_height = height_map[pos];
+        // Work backwards to every square that can step here.
+        for neighbour in height_map.neighbours4(pos) {
+            if height_map[neighbour] >= current_height - 1 && distances[neighbour].is_none() {
+                distances[neighbour] = Some(d + 1);
+                to_visit.push_back(neighbour);
+            }
+        }
+    }
+    distances
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Challenge;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Challenge> {
+        parse_height_map(input)
+    }
+
+    fn part1(challenge: &Challenge) -> eyre::Result<usize> {
+        let distances = distances_to_end(challenge);
+        distances[challenge.start].ok_or_else(|| eyre::eyre!("No route from the start to the end"))
+    }
+
+    fn part2(challenge: &Challenge) -> eyre::Result<usize> {
+        let distances = distances_to_end(challenge);
+        challenge
+            .height_map
+            .iter()
+            .filter(|(_, height)| **height == 'a' as i32)
+            .filter_map(|(pos, _)| distances[pos])
+            .min()
+            .ok_or_else(|| eyre::eyre!("No route from any 'a' to the end"))
+    }
+}
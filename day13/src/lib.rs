@@ -0,0 +1,124 @@
+use std::cmp::Ordering;
+
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+
+#[derive(Clone, Debug)]
+pub enum Packet {
+    Integer(i64),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Packet) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(l), Packet::Integer(r)) => l.cmp(r),
+            (Packet::List(_), Packet::Integer(_)) => self.cmp(&Packet::List(vec![other.clone()])),
+            (Packet::Integer(_), Packet::List(_)) => Packet::List(vec![self.clone()]).cmp(other),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Packet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Packet) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+fn parse_packet(line: &mut Line) -> Result<Packet, ParseError> {
+    if !line.eat("[") {
+        return if line.rest().starts_with(|c: char| c.is_ascii_digit()) {
+            Ok(Packet::Integer(line.number()?))
+        } else {
+            Err(line.expected("a number or \"[\""))
+        };
+    }
+    let mut list = Vec::new();
+    if !line.eat("]") {
+        loop {
+            list.push(parse_packet(line)?);
+            if line.eat("]") {
+                break;
+            }
+            line.tag(",")?;
+        }
+    }
+    Ok(Packet::List(list))
+}
+
+fn line_to_packet(mut line: Line) -> Result<Packet, ParseError> {
+    let packet = parse_packet(&mut line)?;
+    line.end()?;
+    Ok(packet)
+}
+
+fn parse_pairs(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    let mut lines = lines(input);
+    let mut pairs = Vec::new();
+    while let Some(first) = lines.next() {
+        let second = lines
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(first.line_number() + 1, "a second packet"))?;
+        pairs.push((line_to_packet(first)?, line_to_packet(second)?));
+        // Pairs are separated by a blank line.
+        if let Some(blank) = lines.next() {
+            if !blank.is_empty() {
+                return Err(blank.expected("a blank line"));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Vec<(Packet, Packet)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Ok(parse_pairs(input)?)
+    }
+
+    fn part1(pairs: &Self::Input) -> eyre::Result<usize> {
+        let mut correct_sum = 0;
+        for (idx, (line1, line2)) in pairs.iter().enumerate() {
+            if line1 < line2 {
+                correct_sum += idx + 1;
+            }
+        }
+        Ok(correct_sum)
+    }
+
+    fn part2(pairs: &Self::Input) -> eyre::Result<usize> {
+        let mut all_values: Vec<Packet> = Vec::new();
+        for (line1, line2) in pairs {
+            all_values.push(line1.to_owned());
+            all_values.push(line2.to_owned());
+        }
+        let divider = |n| Packet::List(vec![Packet::List(vec![Packet::Integer(n)])]);
+        let special_packets = [divider(2), divider(6)];
+        special_packets
+            .iter()
+            .for_each(|p| all_values.push(p.to_owned()));
+        all_values.sort();
+
+        let special_packet_product: usize = special_packets
+            .iter()
+            .map(|p| all_values.partition_point(|f| f < p) + 1)
+            .product();
+        Ok(special_packet_product)
+    }
+}
@@ -0,0 +1,150 @@
+use aoc_common::{
+    grid::SignedPos,
+    parser::{lines, Line},
+    Params, ParseError, Solver, SparseGrid,
+};
+
+const SAND_SOURCE: SignedPos = (500, 0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Block {
+    Sand,
+    Rock,
+}
+
+fn coordinate_to_tuple(line: &mut Line) -> Result<SignedPos, ParseError> {
+    let x = line.number()?;
+    line.tag(",")?;
+    Ok((x, line.number()?))
+}
+
+// Draws a straight line of rock, or returns None if it's diagonal.
+fn draw_rock_line(from: SignedPos, to: SignedPos, world: &mut SparseGrid<Block>) -> Option<()> {
+    if from.0 == to.0 {
+        // Vertical line, make sure it's top to bottom.
+        let (from, to) = (from.min(to), from.max(to));
+        for y in from.1..=to.1 {
+            world.insert((from.0, y), Block::Rock);
+        }
+        Some(())
+    } else if from.1 == to.1 {
+        // Horizontal line, make sure it's left to right.
+        let (from, to) = (from.min(to), from.max(to));
+        for x in from.0..=to.0 {
+            world.insert((x, from.1), Block::Rock);
+        }
+        Some(())
+    } else {
+        None
+    }
+}
+
+fn add_rock_from_line(mut line: Line, world: &mut SparseGrid<Block>) -> Result<(), ParseError> {
+    let mut from = coordinate_to_tuple(&mut line)?;
+    // Special case of a single rock
+    world.insert(from, Block::Rock);
+    while !line.is_empty() {
+        line.tag(" -> ")?;
+        let start = line.clone();
+        let to = coordinate_to_tuple(&mut line)?;
+        draw_rock_line(from, to, world).ok_or_else(|| {
+            start.error(format!(
+                "expected a straight line from {from:?}, found {to:?}"
+            ))
+        })?;
+        from = to;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum Bottom {
+    // Sand falling below this depth is lost forever.
+    Abyss(i64),
+    // An infinite floor at this depth.
+    Floor(i64),
+}
+
+// Drops a unit of sand, returning false if it didn't come to rest because the
+// source is already blocked or it fell into the abyss.
+fn add_sand(world: &mut SparseGrid<Block>, bottom: Bottom) -> bool {
+    let (mut x, mut y) = SAND_SOURCE;
+    if world.contains((x, y)) {
+        return false;
+    }
+    loop {
+        match bottom {
+            Bottom::Abyss(depth) if y > depth => return false,
+            Bottom::Floor(floor_y) if y + 1 == floor_y => {
+                // Landed on the floor.
+                break;
+            }
+            _ => {}
+        }
+        if !world.contains((x, y + 1)) {
+            // Can fall down.
+            y += 1;
+        } else if !world.contains((x - 1, y + 1)) {
+            // Can fall down-left.
+            x -= 1;
+            y += 1;
+        } else if !world.contains((x + 1, y + 1)) {
+            // Can fall down-right.
+            x += 1;
+            y += 1;
+        } else {
+            break;
+        }
+    }
+    // I guess I live here now.
+    world.insert((x, y), Block::Sand);
+    true
+}
+
+fn find_lowest_rock(world: &SparseGrid<Block>) -> eyre::Result<i64> {
+    world
+        .positions()
+        .map(|(_, y)| y)
+        .max()
+        .ok_or_else(|| eyre::eyre!("No rock found!"))
+}
+
+// Pours sand until it stops coming to rest, returning how much settled.
+fn pour_sand(world: &SparseGrid<Block>, bottom: Bottom) -> usize {
+    let mut world = world.clone();
+    let mut sand_added = 0;
+    while add_sand(&mut world, bottom) {
+        sand_added += 1;
+    }
+    sand_added
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    // The rock in the cave, keyed by (x, y).
+    type Input = SparseGrid<Block>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        let mut world = SparseGrid::new();
+        // Load the rock
+        for line in lines(input) {
+            add_rock_from_line(line, &mut world)?;
+        }
+        Ok(world)
+    }
+
+    fn part1(world: &Self::Input) -> eyre::Result<usize> {
+        // Nothing below the lowest rock.
+        let depth = find_lowest_rock(world)?;
+        Ok(pour_sand(world, Bottom::Abyss(depth)))
+    }
+
+    fn part2(world: &Self::Input) -> eyre::Result<usize> {
+        // There's an infinite rock layer two below the lowest rock.
+        let floor_y = find_lowest_rock(world)? + 2;
+        Ok(pour_sand(world, Bottom::Floor(floor_y)))
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeSet;
+
+use aoc_common::{
+    parser::{lines, Line},
+    Param, Params, ParseError, Solver,
+};
+
+#[derive(Debug)]
+pub struct Sensor {
+    position: (i32, i32),
+    closest_beacon: (i32, i32),
+}
+
+impl Sensor {
+    fn distance_from_beacon(&self) -> u32 {
+        self.position.0.abs_diff(self.closest_beacon.0)
+            + self.position.1.abs_diff(self.closest_beacon.1)
+    }
+}
+
+fn coordinates(line: &mut Line) -> Result<(i32, i32), ParseError> {
+    line.tag("x=")?;
+    let x = line.number()?;
+    line.tag(", y=")?;
+    Ok((x, line.number()?))
+}
+
+fn line_to_sensor(mut line: Line) -> Result<Sensor, ParseError> {
+    line.tag("Sensor at ")?;
+    let position = coordinates(&mut line)?;
+    line.tag(": closest beacon is at ")?;
+    let closest_beacon = coordinates(&mut line)?;
+    line.end()?;
+    Ok(Sensor {
+        position,
+        closest_beacon,
+    })
+}
+
+// Counts the positions in `row` that can't contain a beacon.
+fn count_excluded(sensors: &[Sensor], row: i32) -> usize {
+    let mut ranges: Vec<(i32, i32)> = sensors
+        .iter()
+        .filter_map(|sensor| {
+            let row_distance = sensor.position.1.abs_diff(row);
+            let distance_from_beacon = sensor.distance_from_beacon();
+            if row_distance > distance_from_beacon {
+                return None;
+            }
+            let x_width = (distance_from_beacon - row_distance) as i32;
+            Some((sensor.position.0 - x_width, sensor.position.0 + x_width))
+        })
+        .collect();
+    ranges.sort();
+    // Merge the overlapping ranges and add up what's covered.
+    let mut covered = 0;
+    let mut end_so_far = None;
+    for (start, end) in ranges {
+        let start = match end_so_far {
+            Some(e) if start <= e => e + 1,
+            _ => start,
+        };
+        if end >= start {
+            covered += (end - start + 1) as usize;
+        }
+        end_so_far = end_so_far.max(Some(end));
+    }
+    // Known beacons on the row don't count as places a beacon can't be.
+    let beacons_in_row: BTreeSet<_> = sensors
+        .iter()
+        .filter(|s| s.closest_beacon.1 == row)
+        .map(|s| s.closest_beacon.0)
+        .collect();
+    covered - beacons_in_row.len()
+}
+
+fn find_frequency(sensors: &[Sensor], max: usize) -> eyre::Result<u64> {
+    for j in 0..max + 1 {
+        let mut i = 0;
+        while i <= max as i32 {
+            let x = i;
+            for sensor in sensors {
+                let distance_from_beacon = sensor.distance_from_beacon();
+                let row_distance = sensor.position.1.abs_diff(j as i32);
+                if row_distance > distance_from_beacon {
+                    continue;
+                }
+                let x_width = (distance_from_beacon - row_distance) as i32;
+                let start_impossible_x = sensor.position.0 - x_width;
+                let end_impossible_x = sensor.position.0 + x_width;
+                if i >= start_impossible_x && i <= end_impossible_x {
+                    i = end_impossible_x + 1;
+                    break;
+                }
+            }
+            if x == i {
+                return Ok((x as u64 * 4000000) + (j as u64));
+            }
+        }
+    }
+    Err(eyre::eyre!("No possible beacon position within {max}"))
+}
+
+pub struct Scan {
+    // The sensors, sorted by x position.
+    sensors: Vec<Sensor>,
+    row: i32,
+    max: usize,
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Scan;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "row",
+            default: "2000000",
+            help: "Row to count the positions without a beacon in",
+        },
+        Param {
+            name: "max",
+            default: "4000000",
+            help: "Largest x and y the distress beacon can be at",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input> {
+        let mut sensors = Vec::<Sensor>::new();
+        // Read the sensors.
+        for line in lines(input) {
+            sensors.push(line_to_sensor(line)?)
+        }
+        // Sort by the x value - might help with the later embedded X loop...
+        sensors.sort_by_key(|s| s.position.0);
+        Ok(Scan {
+            sensors,
+            row: params.get("row")?,
+            max: params.get("max")?,
+        })
+    }
+
+    fn part1(scan: &Self::Input) -> eyre::Result<usize> {
+        Ok(count_excluded(&scan.sensors, scan.row))
+    }
+
+    fn part2(scan: &Self::Input) -> eyre::Result<u64> {
+        find_frequency(&scan.sensors, scan.max)
+    }
+}
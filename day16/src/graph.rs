@@ -0,0 +1,200 @@
+//! The tunnels between valves as a graph, and the shortest routes through it.
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// A valve, numbered in the order the valves were first named.
+pub type Id = usize;
+
+/// Valves joined by tunnels that each take a minute to walk.
+#[derive(Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: BTreeMap<String, Id>,
+    links: Vec<Vec<Id>>,
+}
+
+impl Graph {
+    /// The id for a valve, adding it if it's new.
+    pub fn intern(&mut self, name: &str) -> Id {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        self.links.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<Id> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: Id) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_tunnel(&mut self, from: Id, to: Id) {
+        self.links[from].push(to);
+    }
+
+    pub fn neighbours(&self, id: Id) -> &[Id] {
+        &self.links[id]
+    }
+
+    // Walks out from `from`, giving the valve each one was first reached from.
+    fn search(&self, from: Id) -> Vec<Option<(Id, u32)>> {
+        let mut reached = vec![None; self.len()];
+        reached[from] = Some((from, 0));
+        let mut to_visit = VecDeque::from([from]);
+        while let Some(id) = to_visit.pop_front() {
+            let (_, distance) = reached[id].expect("visited valves are reached");
+            for next in &self.links[id] {
+                if reached[*next].is_none() {
+                    reached[*next] = Some((id, distance + 1));
+                    to_visit.push_back(*next);
+                }
+            }
+        }
+        reached
+    }
+
+    /// How many minutes it takes to walk from `from` to each valve, or `None`
+    /// for valves that can't be reached.
+    pub fn distances_from(&self, from: Id) -> Vec<Option<u32>> {
+        self.search(from)
+            .into_iter()
+            .map(|r| r.map(|(_, distance)| distance))
+            .collect()
+    }
+
+    /// The valves along a shortest route from `from` to `to`, not counting
+    /// `from` itself.
+    pub fn path(&self, from: Id, to: Id) -> Option<Vec<Id>> {
+        let reached = self.search(from);
+        let mut path = Vec::new();
+        let mut at = to;
+        while at != from {
+            path.push(at);
+            at = reached[at]?.0;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The distances between `start` and the valves `keep` picks out, leaving
+    /// out every valve in between.
+    pub fn compress(&self, start: Id, keep: impl Fn(Id) -> bool) -> Compressed {
+        let from_start = self.distances_from(start);
+        let (nodes, unreachable): (Vec<Id>, Vec<Id>) = (0..self.len())
+            .filter(|id| *id == start || keep(*id))
+            .partition(|id| from_start[*id].is_some());
+        let mut index = vec![None; self.len()];
+        for (idx, id) in nodes.iter().enumerate() {
+            index[*id] = Some(idx);
+        }
+        let distances = nodes
+            .iter()
+            .map(|id| {
+                let all = self.distances_from(*id);
+                nodes.iter().map(|to| all[*to]).collect()
+            })
+            .collect();
+        Compressed {
+            nodes,
+            index,
+            distances,
+            unreachable,
+        }
+    }
+}
+
+/// The shortest distances between a few valves of a graph.
+#[derive(Debug)]
+pub struct Compressed {
+    /// The valves kept, in id order.
+    pub nodes: Vec<Id>,
+    // Where each valve of the whole graph is in `nodes`, if it's there.
+    index: Vec<Option<usize>>,
+    distances: Vec<Vec<Option<u32>>>,
+    /// The valves that would have been kept but can't be reached from the
+    /// start.
+    pub unreachable: Vec<Id>,
+}
+
+impl Compressed {
+    /// The distance from one kept valve to another, given by their ids in the
+    /// whole graph, or `None` if there's no way there.
+    pub fn distance(&self, from: Id, to: Id) -> Option<u32> {
+        self.distances[self.index[from]?][self.index[to]?]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A - B - C, with D - E off on their own and a one way tunnel from E to C.
+    fn graph() -> Graph {
+        let mut graph = Graph::default();
+        for name in ["A", "B", "C", "D", "E"] {
+            graph.intern(name);
+        }
+        for (from, to) in [(0, 1), (1, 2), (3, 4)] {
+            graph.add_tunnel(from, to);
+            graph.add_tunnel(to, from);
+        }
+        graph.add_tunnel(4, 2);
+        graph
+    }
+
+    #[test]
+    fn interning() {
+        let mut graph = graph();
+        assert_eq!(graph.intern("C"), 2);
+        assert_eq!(graph.len(), 5);
+        assert_eq!((graph.id("E"), graph.id("F")), (Some(4), None));
+        assert_eq!(graph.name(3), "D");
+    }
+
+    #[test]
+    fn paths() {
+        let graph = graph();
+        assert_eq!(
+            graph.distances_from(0),
+            [Some(0), Some(1), Some(2), None, None]
+        );
+        assert_eq!(graph.path(0, 2), Some(vec![1, 2]));
+        assert_eq!(graph.path(0, 0), Some(vec![]));
+        assert_eq!(graph.path(0, 3), None);
+        assert_eq!(graph.path(3, 0), Some(vec![4, 2, 1, 0]));
+    }
+
+    #[test]
+    fn compressing() {
+        let graph = graph();
+        let routes = graph.compress(0, |id| id != 1);
+        assert_eq!(routes.nodes, [0, 2]);
+        assert_eq!(routes.unreachable, [3, 4]);
+        assert_eq!(routes.distance(0, 2), Some(2));
+        assert_eq!(routes.distance(2, 0), Some(2));
+        // Valves left out have no distances.
+        assert_eq!(routes.distance(0, 1), None);
+        assert_eq!(routes.distance(0, 3), None);
+
+        // From the other side, everything can be reached.
+        let routes = graph.compress(3, |id| id != 1);
+        assert_eq!(routes.nodes, [0, 2, 3, 4]);
+        assert!(routes.unreachable.is_empty());
+        assert_eq!(routes.distance(3, 0), Some(4));
+        assert_eq!(routes.distance(0, 3), None);
+    }
+}
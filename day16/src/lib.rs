@@ -0,0 +1,674 @@
+use std::{cmp::Reverse, collections::BTreeMap, fs, path::PathBuf};
+
+use aoc_common::{
+    diag,
+    json::Json,
+    parser::{lines, Line},
+    Param, Params, Solver,
+};
+
+pub mod graph;
+
+use graph::{Graph, Id};
+
+#[derive(Debug)]
+pub struct ValveMap {
+    graph: Graph,
+    // Indexed by valve id.
+    flow_rates: Vec<u32>,
+    start: Id,
+    time_limit: u32,
+    teaching_time: u32,
+    agents: usize,
+    open_time: u32,
+    show_schedule: bool,
+    schedule_json: String,
+}
+
+/// A valve an agent opens, and the minute it spends opening it.
+#[derive(Clone, Debug)]
+pub struct Opening {
+    pub valve: String,
+    pub minute: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Move,
+    Open,
+    Wait,
+}
+
+/// What an agent does in one minute, where it ends up, and how much pressure
+/// the valves it has opened have released by the end of it.
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub minute: u32,
+    pub action: Action,
+    pub position: String,
+    pub released: u32,
+}
+
+/// The valves an agent opens, in order, and everything it does to open them.
+#[derive(Clone, Debug)]
+pub struct AgentPlan {
+    pub openings: Vec<Opening>,
+    pub schedule: Vec<Step>,
+}
+
+/// What each agent does and the pressure they release together.
+#[derive(Clone, Debug)]
+pub struct Plan {
+    pub released: u32,
+    pub time_limit: u32,
+    pub agents: Vec<AgentPlan>,
+}
+
+fn parse_input(input: &str, params: &Params) -> eyre::Result<ValveMap> {
+    let mut graph = Graph::default();
+    let mut flow_rates = Vec::new();
+    // Where each tunnel was given and the valve it's from, to check they all
+    // lead somewhere once every valve is known.
+    let mut tunnels_at = Vec::<(Id, Line)>::new();
+
+    for mut line in lines(input) {
+        line.tag("Valve ")?;
+        let start = line.clone();
+        let valve_name = line.word()?;
+        if graph.id(valve_name).is_some() {
+            return Err(start
+                .error(format!("valve {valve_name} is given twice"))
+                .into());
+        }
+        line.tag(" has flow rate=")?;
+        let flow_rate: u32 = line.number()?;
+        line.one_of(&[
+            ("; tunnels lead to valves ", ()),
+            ("; tunnel leads to valve ", ()),
+        ])?;
+        let id = graph.intern(valve_name);
+        loop {
+            tunnels_at.push((id, line.clone()));
+            line.word()?;
+            if line.is_empty() {
+                break;
+            }
+            line.tag(", ")?;
+        }
+        flow_rates.push(flow_rate);
+    }
+    for (from, mut at) in tunnels_at {
+        let name = at.word()?;
+        let Some(to) = graph.id(name) else {
+            return Err(at.error(format!("there is no valve {name}")).into());
+        };
+        graph.add_tunnel(from, to);
+    }
+    let start_valve: String = params.get("start-valve")?;
+    let start = graph
+        .id(&start_valve)
+        .ok_or_else(|| eyre::eyre!("There is no valve {start_valve} to start at"))?;
+    let agents = params.get("agents")?;
+    if agents == 0 {
+        return Err(eyre::eyre!("There must be at least one agent"));
+    }
+    Ok(ValveMap {
+        graph,
+        flow_rates,
+        start,
+        time_limit: params.get("time-limit")?,
+        teaching_time: params.get("teaching-time")?,
+        agents,
+        open_time: params.get("open-time")?,
+        show_schedule: params.get("show-schedule")?,
+        schedule_json: params.get("schedule-json")?,
+    })
+}
+
+// The search only looks at the valves worth opening, numbered from 0 by their
+// bit in a mask of opened valves, with the start after them.
+struct Search<'a> {
+    valve_map: &'a ValveMap,
+    // The id and flow rate of each valve worth opening.
+    valves: Vec<(Id, u32)>,
+    // Minutes to walk from one valve (or the start) to another and open it,
+    // a row for each, or `None` if there's no way there.
+    costs: Vec<Option<u32>>,
+}
+
+// The best order found so far to open some valves in, and what it releases.
+type Route = (u32, Vec<(usize, u32)>);
+
+// Up to this many valves, the best release for every mask fits in a table
+// (4 MiB at most), which is much quicker to update than a map.
+const MAX_TABLE_VALVES: usize = 20;
+
+// The most released so far for each mask of opened valves.
+enum BestPerMask {
+    // Indexed by mask. Opening any valve releases something, so only the
+    // empty mask can be reached and still hold 0.
+    Table(Vec<u32>),
+    Map(BTreeMap<u64, u32>),
+}
+
+impl BestPerMask {
+    fn new(valves: usize) -> BestPerMask {
+        if valves <= MAX_TABLE_VALVES {
+            BestPerMask::Table(vec![0; 1 << valves])
+        } else {
+            BestPerMask::Map(BTreeMap::new())
+        }
+    }
+
+    fn record(&mut self, mask: u64, released: u32) {
+        let best = match self {
+            BestPerMask::Table(table) => &mut table[mask as usize],
+            BestPerMask::Map(map) => map.entry(mask).or_insert(0),
+        };
+        *best = released.max(*best);
+    }
+
+    fn into_vec(self) -> Vec<(u64, u32)> {
+        match self {
+            BestPerMask::Table(table) => table
+                .into_iter()
+                .enumerate()
+                .filter(|(mask, released)| *mask == 0 || *released > 0)
+                .map(|(mask, released)| (mask as u64, released))
+                .collect(),
+            BestPerMask::Map(map) => map.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> Search<'a> {
+    fn new(valve_map: &'a ValveMap) -> eyre::Result<Search<'a>> {
+        let graph = &valve_map.graph;
+        let routes = graph.compress(valve_map.start, |id| valve_map.flow_rates[id] > 0);
+        if !routes.unreachable.is_empty() {
+            let names: Vec<_> = routes
+                .unreachable
+                .iter()
+                .map(|id| graph.name(*id))
+                .collect();
+            diag!(
+                "Can't reach {} from {}, so they stay closed",
+                names.join(", "),
+                graph.name(valve_map.start)
+            );
+        }
+        let valves: Vec<_> = routes
+            .nodes
+            .iter()
+            .map(|id| (*id, valve_map.flow_rates[*id]))
+            .filter(|(_, f)| *f > 0)
+            .collect();
+        if valves.len() > u64::BITS as usize {
+            return Err(eyre::eyre!(
+                "Only {} valves with any flow can be opened, not {}",
+                u64::BITS,
+                valves.len()
+            ));
+        }
+        let routes = &routes;
+        let from = valves.iter().map(|(id, _)| *id).chain([valve_map.start]);
+        let costs = from
+            .flat_map(|from| {
+                valves
+                    .iter()
+                    .map(move |(to, _)| Some(routes.distance(from, *to)? + valve_map.open_time))
+            })
+            .collect();
+        Ok(Search {
+            valve_map,
+            valves,
+            costs,
+        })
+    }
+
+    fn start(&self) -> usize {
+        self.valves.len()
+    }
+
+    // Walking to a valve then opening it, if that leaves any time for it to
+    // release pressure.
+    fn time_left_after(&self, from: usize, to: usize, time_left: u32) -> Option<u32> {
+        let cost = self.costs[from * self.valves.len() + to]?;
+        time_left.checked_sub(cost).filter(|t| *t > 0)
+    }
+
+    // The most pressure that can be released by opening exactly the valves in
+    // each mask within `time_limit`, for every mask that can be opened in time.
+    fn best_per_mask(&self, time_limit: u32) -> Vec<(u64, u32)> {
+        let mut best = BestPerMask::new(self.valves.len());
+        self.explore(self.start(), time_limit, 0, 0, &mut best);
+        best.into_vec()
+    }
+
+    // Each valve is credited with everything it will release once opened, so
+    // `released` is the total by the end of the time.
+    fn explore(
+        &self,
+        at: usize,
+        time_left: u32,
+        opened: u64,
+        released: u32,
+        best: &mut BestPerMask,
+    ) {
+        best.record(opened, released);
+        for (valve, (_, rate)) in self.valves.iter().enumerate() {
+            if opened & 1 << valve != 0 {
+                continue;
+            }
+            if let Some(time_left) = self.time_left_after(at, valve, time_left) {
+                self.explore(
+                    valve,
+                    time_left,
+                    opened | 1 << valve,
+                    released + rate * time_left,
+                    best,
+                );
+            }
+        }
+    }
+
+    // Finds the order to open exactly the valves in `mask` that releases the
+    // most, as the table only keeps how much that is. Gives the id of each
+    // valve and the minute it's opened in.
+    fn route(&self, mask: u64, time_limit: u32) -> Vec<(Id, u32)> {
+        let mut best = None;
+        let mut route = Vec::with_capacity(mask.count_ones() as usize);
+        self.order(
+            self.start(),
+            (time_limit, time_limit),
+            mask,
+            0,
+            &mut route,
+            &mut best,
+        );
+        best.map(|(_, route)| route)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(valve, minute)| (self.valves[valve].0, minute))
+            .collect()
+    }
+
+    fn order(
+        &self,
+        at: usize,
+        (time_limit, time_left): (u32, u32),
+        to_open: u64,
+        released: u32,
+        route: &mut Vec<(usize, u32)>,
+        best: &mut Option<Route>,
+    ) {
+        if to_open == 0 {
+            if best.as_ref().is_none_or(|(b, _)| released > *b) {
+                *best = Some((released, route.clone()));
+            }
+            return;
+        }
+        for (valve, (_, rate)) in self.valves.iter().enumerate() {
+            if to_open & 1 << valve == 0 {
+                continue;
+            }
+            if let Some(time_left) = self.time_left_after(at, valve, time_left) {
+                route.push((valve, time_limit - time_left));
+                self.order(
+                    valve,
+                    (time_limit, time_left),
+                    to_open & !(1 << valve),
+                    released + rate * time_left,
+                    route,
+                    best,
+                );
+                route.pop();
+            }
+        }
+    }
+
+    // Plays out a route minute by minute, walking the shortest way to each
+    // valve in turn.
+    fn schedule(&self, route: &[(Id, u32)], time_limit: u32) -> Vec<Step> {
+        let graph = &self.valve_map.graph;
+        let mut steps = Vec::new();
+        let mut at = self.valve_map.start;
+        let (mut rate, mut released) = (0, 0);
+        let mut route = route.iter().peekable();
+        for minute in 1..=time_limit {
+            released += rate;
+            let action = match route.peek() {
+                None => Action::Wait,
+                Some((valve, opened)) if *valve == at => {
+                    if *opened == minute {
+                        rate += self.valve_map.flow_rates[at];
+                        route.next();
+                    }
+                    Action::Open
+                }
+                Some((valve, _)) => {
+                    let path = graph
+                        .path(at, *valve)
+                        .expect("planned valves can be reached");
+                    at = path[0];
+                    Action::Move
+                }
+            };
+            steps.push(Step {
+                minute,
+                action,
+                position: graph.name(at).to_owned(),
+                released,
+            });
+        }
+        steps
+    }
+}
+
+// Picks a different set of valves from `by_release` for each of `agents` more
+// agents, keeping the choice that releases the most. Sets are only picked in
+// the order given, so each combination is only tried once.
+fn share(
+    by_release: &[(u64, u32)],
+    agents: usize,
+    (from, opened, released): (usize, u64, u32),
+    chosen: &mut Vec<u64>,
+    best: &mut Option<(u32, Vec<u64>)>,
+) {
+    if agents == 0 {
+        if best.as_ref().is_none_or(|(b, _)| released > *b) {
+            *best = Some((released, chosen.clone()));
+        }
+        return;
+    }
+    for (idx, (mask, release)) in by_release.iter().enumerate().skip(from) {
+        // Everything later releases no more than this, so stop once even
+        // giving this to every agent couldn't do better.
+        if let Some((b, _)) = best {
+            if released + release * agents as u32 <= *b {
+                break;
+            }
+        }
+        if mask & opened == 0 {
+            chosen.push(*mask);
+            share(
+                by_release,
+                agents - 1,
+                (idx, opened | mask, released + release),
+                chosen,
+                best,
+            );
+            chosen.pop();
+        }
+    }
+}
+
+/// The most pressure `agents` can release together in `time_limit` minutes,
+/// each opening different valves, and the order each opens them in.
+pub fn plan(valve_map: &ValveMap, agents: usize, time_limit: u32) -> eyre::Result<Plan> {
+    let search = Search::new(valve_map)?;
+    let mut by_release = search.best_per_mask(time_limit);
+    by_release.sort_by_key(|(_, released)| Reverse(*released));
+    let mut best = None;
+    share(&by_release, agents, (0, 0, 0), &mut Vec::new(), &mut best);
+    // Opening nothing is always possible, so there's always a best.
+    let (released, masks) = best.expect("some plan");
+    let agents = masks
+        .into_iter()
+        .map(|mask| {
+            let route = search.route(mask, time_limit);
+            let schedule = search.schedule(&route, time_limit);
+            let openings = route
+                .into_iter()
+                .map(|(id, minute)| Opening {
+                    valve: valve_map.graph.name(id).to_owned(),
+                    minute,
+                })
+                .collect();
+            AgentPlan { openings, schedule }
+        })
+        .collect();
+    Ok(Plan {
+        released,
+        time_limit,
+        agents,
+    })
+}
+
+fn describe(step: &Step) -> String {
+    let action = match step.action {
+        Action::Move => "move to",
+        Action::Open => "open",
+        Action::Wait => "wait at",
+    };
+    format!(
+        "Minute {}: {action} {}, {} released",
+        step.minute, step.position, step.released
+    )
+}
+
+fn plan_to_json(plan: &Plan) -> Json {
+    let agents: Vec<Json> = plan
+        .agents
+        .iter()
+        .map(|agent| {
+            let openings: Vec<Json> = agent
+                .openings
+                .iter()
+                .map(|o| {
+                    Json::object()
+                        .with("valve", o.valve.as_str())
+                        .with("minute", o.minute)
+                })
+                .collect();
+            let schedule: Vec<Json> = agent
+                .schedule
+                .iter()
+                .map(|step| {
+                    let action = match step.action {
+                        Action::Move => "move",
+                        Action::Open => "open",
+                        Action::Wait => "wait",
+                    };
+                    Json::object()
+                        .with("minute", step.minute)
+                        .with("action", action)
+                        .with("position", step.position.as_str())
+                        .with("released", step.released)
+                })
+                .collect();
+            Json::object()
+                .with("openings", openings)
+                .with("schedule", schedule)
+        })
+        .collect();
+    Json::object()
+        .with("released", plan.released)
+        .with("time_limit", plan.time_limit)
+        .with("agents", agents)
+}
+
+// Shows how the plan for a part plays out, as asked for by the parameters.
+fn report(valve_map: &ValveMap, plan: &Plan, part: &str) -> eyre::Result<()> {
+    for (idx, agent) in plan.agents.iter().enumerate() {
+        let opens: Vec<_> = agent
+            .openings
+            .iter()
+            .map(|o| format!("{} in minute {}", o.valve, o.minute))
+            .collect();
+        diag!(
+            "Agent {} opens {}",
+            idx + 1,
+            if opens.is_empty() {
+                "nothing".to_owned()
+            } else {
+                opens.join(", ")
+            }
+        );
+        if valve_map.show_schedule {
+            for step in &agent.schedule {
+                diag!("  {}", describe(step));
+            }
+        }
+    }
+    if !valve_map.schedule_json.is_empty() {
+        // Each part gets its own file, named after it.
+        let mut path = PathBuf::from(&valve_map.schedule_json);
+        let extension = path.extension().map(|e| e.to_string_lossy().into_owned());
+        path.set_extension(part);
+        if let Some(extension) = extension {
+            path.set_extension(format!("{part}.{extension}"));
+        }
+        fs::write(&path, format!("{}\n", plan_to_json(plan)))
+            .map_err(|e| eyre::eyre!("Failed to write {}: {e}", path.display()))?;
+        diag!("Wrote the plan to {}", path.display());
+    }
+    Ok(())
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = ValveMap;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "start-valve",
+            default: "AA",
+            help: "The valve everyone starts at",
+        },
+        Param {
+            name: "time-limit",
+            default: "30",
+            help: "Minutes until the volcano erupts",
+        },
+        Param {
+            name: "teaching-time",
+            default: "4",
+            help: "Minutes spent teaching the elephants in part 2",
+        },
+        Param {
+            name: "agents",
+            default: "2",
+            help: "How many work together in part 2, counting you",
+        },
+        Param {
+            name: "open-time",
+            default: "1",
+            help: "Minutes it takes to open a valve",
+        },
+        Param {
+            name: "show-schedule",
+            default: "false",
+            help: "Print what each agent does minute by minute",
+        },
+        Param {
+            name: "schedule-json",
+            default: "",
+            help: "Write each part's plan as JSON, to this name with the part added",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<ValveMap> {
+        parse_input(input, params)
+    }
+
+    fn part1(valve_map: &ValveMap) -> eyre::Result<u32> {
+        // On our own for the whole time.
+        let plan = plan(valve_map, 1, valve_map.time_limit)?;
+        report(valve_map, &plan, "part1")?;
+        Ok(plan.released)
+    }
+
+    fn part2(valve_map: &ValveMap) -> eyre::Result<u32> {
+        // Spend some time teaching the elephants, then split the valves between us.
+        let time_limit = valve_map
+            .time_limit
+            .checked_sub(valve_map.teaching_time)
+            .ok_or_else(|| eyre::eyre!("No time left after teaching the elephants"))?;
+        let plan = plan(valve_map, valve_map.agents, time_limit)?;
+        report(valve_map, &plan, "part2")?;
+        Ok(plan.released)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::diagnostics;
+
+    use super::*;
+
+    // Plays out an agent's schedule, checking each move follows a tunnel and
+    // each valve is opened where and when it says, giving what it releases.
+    fn replay(valve_map: &ValveMap, agent: &AgentPlan, time_limit: u32) -> u32 {
+        let graph = &valve_map.graph;
+        let mut at = valve_map.start;
+        let (mut rate, mut released) = (0, 0);
+        let mut openings = agent.openings.iter().peekable();
+        assert_eq!(agent.schedule.len(), time_limit as usize);
+        for (minute, step) in (1..).zip(&agent.schedule) {
+            assert_eq!(step.minute, minute);
+            released += rate;
+            let next = graph.id(&step.position).unwrap();
+            match step.action {
+                Action::Move => assert!(graph.neighbours(at).contains(&next)),
+                Action::Open | Action::Wait => assert_eq!(next, at),
+            }
+            at = next;
+            if let Some(opening) = openings.next_if(|o| o.minute == minute) {
+                assert_eq!(
+                    (step.action, step.position.as_str()),
+                    (Action::Open, &opening.valve[..])
+                );
+                rate += valve_map.flow_rates[at];
+            }
+            assert_eq!(step.released, released, "minute {minute}");
+        }
+        assert!(openings.next().is_none(), "every valve is opened in time");
+        released
+    }
+
+    #[test]
+    fn schedules_add_up() {
+        let valve_map = Day16::parse(include_str!("../demo.txt")).unwrap();
+        for (agents, time_limit, expected) in
+            [(1, 30, Some(1651)), (2, 26, Some(1707)), (3, 20, None)]
+        {
+            let plan = plan(&valve_map, agents, time_limit).unwrap();
+            assert_eq!(plan.agents.len(), agents);
+            let released: u32 = plan
+                .agents
+                .iter()
+                .map(|agent| replay(&valve_map, agent, time_limit))
+                .sum();
+            assert_eq!(released, plan.released);
+            if let Some(expected) = expected {
+                assert_eq!(plan.released, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_valves_stay_closed() {
+        let input = format!(
+            "{}Valve KK has flow rate=50; tunnel leads to valve LL\n\
+             Valve LL has flow rate=0; tunnel leads to valve KK\n",
+            include_str!("../demo.txt")
+        );
+        let valve_map = Day16::parse(&input).unwrap();
+        let (released, messages) = diagnostics::capture(|| Day16::part1(&valve_map));
+        assert_eq!(released.unwrap(), 1651);
+        assert_eq!(messages[0], "Can't reach KK from AA, so they stay closed");
+    }
+
+    #[test]
+    fn map_matches_table() {
+        let valve_map = Day16::parse(include_str!("../demo.txt")).unwrap();
+        let search = Search::new(&valve_map).unwrap();
+        let mut map = BestPerMask::Map(BTreeMap::new());
+        search.explore(search.start(), 30, 0, 0, &mut map);
+        assert_eq!(map.into_vec(), search.best_per_mask(30));
+    }
+}
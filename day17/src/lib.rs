@@ -0,0 +1,474 @@
+use std::{collections::BTreeMap, fs};
+
+use aoc_common::{
+    diag,
+    parser::{lines, Line},
+    Param, Params, ParseError, Solver,
+};
+use eyre::eyre;
+
+mod render;
+
+use render::View;
+
+#[derive(Clone, Copy)]
+pub enum JetDirection {
+    Left,
+    Right,
+}
+
+fn read_jet_pattern(input: &str) -> Result<Vec<JetDirection>, ParseError> {
+    let mut line = lines(input)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(1, "a jet pattern"))?;
+    let mut jets = Vec::new();
+    loop {
+        jets.push(line.one_of(&[("<", JetDirection::Left), (">", JetDirection::Right)])?);
+        if line.is_empty() {
+            return Ok(jets);
+        }
+    }
+}
+
+/// A rock, as a mask of the columns it fills in each row from the bottom up.
+/// Column 0 is the lowest bit.
+#[derive(Clone, Debug)]
+pub struct Shape {
+    rows: Vec<u64>,
+    width: usize,
+}
+
+const MAX_WIDTH: usize = 64;
+
+// The rocks from the puzzle, in the order they fall.
+const DEFAULT_SHAPES: &str = include_str!("../shapes.txt");
+
+// Reads rocks drawn with '#' and '.', separated by blank lines.
+fn parse_shapes(text: &str) -> Result<Vec<Shape>, ParseError> {
+    let mut shapes = Vec::new();
+    let mut drawing: Vec<Line> = Vec::new();
+    for line in lines(text).chain([Line::new(0, "")]) {
+        if !line.is_empty() {
+            drawing.push(line);
+            continue;
+        }
+        if drawing.is_empty() {
+            continue;
+        }
+        let first = drawing[0].clone();
+        let mut rows = Vec::new();
+        let mut width = 0;
+        for mut row in drawing.drain(..).rev() {
+            let cells = row.take_while(|c| c == '#' || c == '.');
+            if !row.is_empty() {
+                return Err(row.expected("'#' or '.'"));
+            }
+            if cells.len() > MAX_WIDTH {
+                return Err(row.error(format!("rocks can be at most {MAX_WIDTH} wide")));
+            }
+            width = width.max(cells.len());
+            rows.push(
+                cells
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .fold(0u64, |mask, (x, _)| mask | 1 << x),
+            );
+        }
+        if rows.iter().all(|row| *row == 0) {
+            return Err(first.error("this rock has no '#' in it"));
+        }
+        shapes.push(Shape { rows, width });
+    }
+    Ok(shapes)
+}
+
+// Reads a comma separated list of rocks, numbered from 1 in the shape file.
+fn parse_sequence(text: &str, shape_count: usize) -> Result<Vec<usize>, ParseError> {
+    let mut line = Line::new(1, text);
+    let mut sequence = Vec::new();
+    loop {
+        let start = line.clone();
+        let rock: usize = line.number()?;
+        if !(1..=shape_count).contains(&rock) {
+            return Err(start.error(format!("there are only {shape_count} rocks")));
+        }
+        sequence.push(rock - 1);
+        if line.is_empty() {
+            return Ok(sequence);
+        }
+        line.tag(",")?;
+    }
+}
+
+pub struct Chamber {
+    jets: Vec<JetDirection>,
+    shapes: Vec<Shape>,
+    // Which shape falls next, as indexes into `shapes`, repeated forever.
+    sequence: Vec<usize>,
+    width: usize,
+    view: View,
+}
+
+// Only the top of the tower is kept, as rocks never fall far into it. Anything
+// below the kept rows counts as solid.
+const BUFFER_SIZE: usize = 100000;
+const BUFFER_DRAIN_SIZE: usize = BUFFER_SIZE / 10;
+
+// How far down each column is looked at when describing the surface of the
+// tower. Deeper gaps than this are all treated the same, so a cycle spotted
+// with one might not really repeat.
+const PROFILE_DEPTH: usize = 64;
+
+struct Tower<'a> {
+    chamber: &'a Chamber,
+    rows: Vec<u64>,
+    y_offset: usize,
+    // The number of rows with rock in, including any dropped off the bottom.
+    height: usize,
+    // How many rocks have fallen, and the next jet to blow.
+    rock_number: usize,
+    jet: usize,
+}
+
+impl<'a> Tower<'a> {
+    fn new(chamber: &'a Chamber) -> Tower<'a> {
+        Tower {
+            chamber,
+            rows: Vec::new(),
+            y_offset: 0,
+            height: 0,
+            rock_number: 0,
+            jet: 0,
+        }
+    }
+
+    fn next_shape(&self) -> &'a Shape {
+        let sequence = &self.chamber.sequence;
+        &self.chamber.shapes[sequence[self.rock_number % sequence.len()]]
+    }
+
+    // Makes sure there are rows up to `max_y`, dropping the bottom of the tower
+    // if that's more than are kept.
+    fn reserve(&mut self, max_y: usize) {
+        if max_y >= self.y_offset + BUFFER_SIZE {
+            self.rows.drain(0..BUFFER_DRAIN_SIZE);
+            self.y_offset += BUFFER_DRAIN_SIZE;
+        }
+        if self.rows.len() + self.y_offset <= max_y {
+            self.rows.resize(max_y + 1 - self.y_offset, 0);
+        }
+    }
+
+    fn fits(&self, shape: &Shape, x: usize, y: usize) -> bool {
+        y >= self.y_offset
+            && x + shape.width <= self.chamber.width
+            && shape
+                .rows
+                .iter()
+                .enumerate()
+                .all(|(dy, row)| self.rows[y + dy - self.y_offset] & (row << x) == 0)
+    }
+
+    // Where the bottom left corner of the next rock appears.
+    fn spawn_point(&self) -> (usize, usize) {
+        (2, self.height + 3)
+    }
+
+    // Drops the next rock until it comes to rest.
+    fn drop_rock(&mut self) {
+        let shape = self.next_shape();
+        let (mut x, mut y) = self.spawn_point();
+        self.reserve(y + shape.rows.len());
+        loop {
+            // Move the rock with the jet (if possible)
+            let jet = self.chamber.jets[self.jet];
+            self.jet = (self.jet + 1) % self.chamber.jets.len();
+            match jet {
+                JetDirection::Left if x > 0 && self.fits(shape, x - 1, y) => x -= 1,
+                JetDirection::Right if self.fits(shape, x + 1, y) => x += 1,
+                _ => {}
+            }
+            // Move the rock down.
+            if y == 0 || !self.fits(shape, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        // Save the rock to the world.
+        for (dy, row) in shape.rows.iter().enumerate() {
+            self.rows[y + dy - self.y_offset] |= row << x;
+        }
+        self.height = self.height.max(y + shape.rows.len());
+        self.rock_number += 1;
+    }
+
+    // How far below the top of the tower the highest rock in each column is,
+    // counting the floor and any dropped rows as rock, up to `PROFILE_DEPTH`.
+    fn profile(&self) -> Vec<usize> {
+        (0..self.chamber.width)
+            .map(|column| {
+                (0..PROFILE_DEPTH)
+                    .find(|d| {
+                        self.height <= d + self.y_offset
+                            || self.rows[self.height - 1 - d - self.y_offset] & 1 << column != 0
+                    })
+                    .unwrap_or(PROFILE_DEPTH)
+            })
+            .collect()
+    }
+}
+
+fn tower_height(chamber: &Chamber, rock_count: usize) -> usize {
+    let mut tower = Tower::new(chamber);
+    // The rock number and height the first time each state was seen, keyed on
+    // the next rock, the next jet and the shape of the top of the tower.
+    let mut seen = BTreeMap::new();
+    let mut skipped_height = 0;
+    while tower.rock_number < rock_count {
+        tower.drop_rock();
+        if skipped_height == 0 {
+            let state = (
+                tower.rock_number % chamber.sequence.len(),
+                tower.jet,
+                tower.profile(),
+            );
+            let too_deep = state.2.contains(&PROFILE_DEPTH);
+            let (rock_number, height) = (tower.rock_number, tower.height);
+            if let Some((first_rock, first_height)) = seen.insert(state, (rock_number, height)) {
+                // Everything from here repeats, so skip as many whole cycles
+                // as fit and simulate what's left over.
+                let (cycle_rocks, cycle_height) = (rock_number - first_rock, height - first_height);
+                let cycles = (rock_count - rock_number) / cycle_rocks;
+                diag!("Rocks {first_rock} to {rock_number} repeat, adding {cycle_height} rows each time");
+                if too_deep {
+                    diag!("Warning: the tower has a gap over {PROFILE_DEPTH} rows deep, so it might not really repeat");
+                }
+                tower.rock_number += cycles * cycle_rocks;
+                skipped_height = cycles * cycle_height;
+                seen.clear();
+            }
+        }
+    }
+    tower.height + skipped_height
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Input = Chamber;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: "7",
+            help: "Width of the chamber, up to 64",
+        },
+        Param {
+            name: "shapes",
+            default: "",
+            help: "File of rocks drawn with '#' and '.', separated by blank lines",
+        },
+        Param {
+            name: "rocks",
+            default: "",
+            help: "Comma separated order the rocks fall in, numbered from 1",
+        },
+        Param {
+            name: "show-rows",
+            default: "0",
+            help: "Rows from the top of the tower to print after part 1",
+        },
+        Param {
+            name: "step",
+            default: "false",
+            help: "Wait for enter before dropping each rock in part 1, printing the top rows",
+        },
+        Param {
+            name: "image",
+            default: "",
+            help: "A .ppm or .png file to draw the whole tower in after part 1",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input> {
+        let width: usize = params.get("width")?;
+        if !(1..=MAX_WIDTH).contains(&width) {
+            return Err(eyre!("The chamber must be from 1 to {MAX_WIDTH} wide"));
+        }
+        let shapes = match params.get::<String>("shapes")?.as_str() {
+            "" => parse_shapes(DEFAULT_SHAPES)?,
+            path => {
+                let text =
+                    fs::read_to_string(path).map_err(|e| eyre!("Failed to read {path}: {e}"))?;
+                parse_shapes(&text).map_err(|e| eyre!("In {path}, {e}"))?
+            }
+        };
+        if shapes.is_empty() {
+            return Err(eyre!("There are no rocks"));
+        }
+        let sequence = match params.get::<String>("rocks")?.as_str() {
+            "" => (0..shapes.len()).collect(),
+            rocks => parse_sequence(rocks, shapes.len()).map_err(|e| eyre!("In --rocks, {e}"))?,
+        };
+        if let Some(shape) = shapes.iter().find(|s| s.width + 2 > width) {
+            return Err(eyre!(
+                "A rock {} wide doesn't fit in a chamber {width} wide",
+                shape.width
+            ));
+        }
+        Ok(Chamber {
+            jets: read_jet_pattern(input)?,
+            shapes,
+            sequence,
+            width,
+            view: View::new(
+                params.get("show-rows")?,
+                params.get("step")?,
+                params.get("image")?,
+            )?,
+        })
+    }
+
+    fn part1(chamber: &Self::Input) -> eyre::Result<usize> {
+        if chamber.view.is_active() {
+            render::watch(chamber, 2022)?;
+        }
+        Ok(tower_height(chamber, 2022))
+    }
+
+    fn part2(chamber: &Self::Input) -> eyre::Result<usize> {
+        Ok(tower_height(chamber, 1000000000000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chamber(width: usize, shapes: &str, sequence: &str) -> Chamber {
+        let shapes = parse_shapes(shapes).unwrap();
+        let sequence = match sequence {
+            "" => (0..shapes.len()).collect(),
+            sequence => parse_sequence(sequence, shapes.len()).unwrap(),
+        };
+        Chamber {
+            jets: read_jet_pattern(include_str!("../demo.txt")).unwrap(),
+            shapes,
+            sequence,
+            width,
+            view: View::new(0, false, String::new()).unwrap(),
+        }
+    }
+
+    fn simulated_height(chamber: &Chamber, rock_count: usize) -> usize {
+        let mut tower = Tower::new(chamber);
+        while tower.rock_number < rock_count {
+            tower.drop_rock();
+        }
+        tower.height
+    }
+
+    fn check_cycles(chamber: &Chamber) {
+        for rock_count in [1, 100, 2022, 3001, 4999] {
+            assert_eq!(
+                tower_height(chamber, rock_count),
+                simulated_height(chamber, rock_count),
+                "after {rock_count} rocks"
+            );
+        }
+    }
+
+    #[test]
+    fn cycles_match_simulation() {
+        let demo = chamber(7, DEFAULT_SHAPES, "");
+        assert_eq!(tower_height(&demo, 2022), 3068);
+        check_cycles(&demo);
+    }
+
+    #[test]
+    fn cycles_match_simulation_with_other_rocks() {
+        let shapes = "#########.\n\n#..\n#..\n###\n\n.#\n##\n";
+        check_cycles(&chamber(12, shapes, "1,2,2,3"));
+        check_cycles(&chamber(64, DEFAULT_SHAPES, "5,4,1"));
+    }
+
+    #[test]
+    fn default_shapes() {
+        let shapes = parse_shapes(DEFAULT_SHAPES).unwrap();
+        let widths: Vec<usize> = shapes.iter().map(|s| s.width).collect();
+        assert_eq!(widths, [4, 3, 3, 1, 2]);
+        assert_eq!(shapes[1].rows, [0b010, 0b111, 0b010]);
+        // Rows go from the bottom up, with column 0 the lowest bit.
+        assert_eq!(shapes[2].rows, [0b111, 0b100, 0b100]);
+    }
+
+    #[test]
+    fn shape_files() {
+        let shapes = parse_shapes("\n\n.#\n###\n\n\n\n#\n\n").unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(
+            (shapes[0].width, &shapes[0].rows[..]),
+            (3, &[0b111, 0b10][..])
+        );
+        assert_eq!((shapes[1].width, &shapes[1].rows[..]), (1, &[1][..]));
+        assert_eq!(parse_shapes("#".repeat(64).as_str()).unwrap()[0].width, 64);
+        assert!(parse_shapes("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn bad_shapes() {
+        let err = parse_shapes("##\n\n#x#\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.message, r#"expected '#' or '.', found "x#""#);
+
+        let err = parse_shapes(&"#".repeat(65)).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.message, "rocks can be at most 64 wide");
+
+        let err = parse_shapes("#\n\n..\n..\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.message, "this rock has no '#' in it");
+    }
+
+    #[test]
+    fn sequences() {
+        assert_eq!(parse_sequence("1,3,2,3", 3).unwrap(), [0, 2, 1, 2]);
+        let err = parse_sequence("0", 3).unwrap_err();
+        assert_eq!(
+            (err.column, &err.message[..]),
+            (1, "there are only 3 rocks")
+        );
+        let err = parse_sequence("1,4", 3).unwrap_err();
+        assert_eq!(
+            (err.column, &err.message[..]),
+            (3, "there are only 3 rocks")
+        );
+        let err = parse_sequence("1;2", 3).unwrap_err();
+        assert_eq!(err.message, r#"expected ",", found ";2""#);
+        let err = parse_sequence("", 3).unwrap_err();
+        assert_eq!(err.message, "expected a number, found end of line");
+        assert!(parse_sequence("1,", 3).is_err());
+    }
+
+    #[test]
+    fn profiles_stop_at_the_cap() {
+        let chamber = chamber(3, "#\n", "");
+        let mut tower = Tower::new(&chamber);
+        // A well in the last column, just within reach.
+        tower.rows = vec![0b111; 10];
+        tower.rows.extend([0b011; PROFILE_DEPTH - 1]);
+        tower.height = tower.rows.len();
+        assert_eq!(tower.profile(), vec![0, 0, PROFILE_DEPTH - 1]);
+        // Then out of reach, where any depth looks the same.
+        tower.rows.push(0b011);
+        tower.height += 1;
+        assert_eq!(tower.profile(), vec![0, 0, PROFILE_DEPTH]);
+        // An empty column goes down to the floor.
+        tower.rows = vec![0b011; 5];
+        tower.height = 5;
+        assert_eq!(tower.profile(), vec![0, 0, 5]);
+    }
+}
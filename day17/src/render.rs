@@ -0,0 +1,353 @@
+//! Pictures of the tower, either as text for the terminal or as an image of
+//! the whole thing.
+
+use std::{
+    fs,
+    io::{self, BufRead},
+    iter,
+};
+
+use aoc_common::diag;
+use eyre::eyre;
+
+use crate::{Chamber, Shape, Tower};
+
+/// What to show of the tower while solving part 1.
+pub struct View {
+    // Rows to print from the top of the tower once the rocks have fallen.
+    pub rows: usize,
+    // Whether to wait for enter before dropping each rock.
+    pub step: bool,
+    pub image: Option<String>,
+}
+
+// How many rows to draw while stepping if no number was given.
+const STEP_ROWS: usize = 20;
+
+impl View {
+    pub fn new(rows: usize, step: bool, image: String) -> eyre::Result<View> {
+        if !(image.is_empty() || image.ends_with(".ppm") || image.ends_with(".png")) {
+            return Err(eyre!("Can only write .ppm or .png images, not {image}"));
+        }
+        Ok(View {
+            rows,
+            step,
+            image: Some(image).filter(|i| !i.is_empty()),
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.rows > 0 || self.step || self.image.is_some()
+    }
+}
+
+// Draws the top `rows` rows of the tower with any falling rock, given with the
+// position of its bottom left corner, as '@'.
+fn draw(tower: &Tower, rows: usize, falling: Option<(&Shape, usize, usize)>) -> String {
+    let top = falling
+        .map_or(0, |(shape, _, y)| y + shape.rows.len())
+        .max(tower.height);
+    let bottom = top.saturating_sub(rows).max(tower.y_offset);
+    let mut picture = String::new();
+    for y in (bottom..top).rev() {
+        let settled = tower.rows.get(y - tower.y_offset).copied().unwrap_or(0);
+        let moving = match falling {
+            Some((shape, x, fy)) if (fy..fy + shape.rows.len()).contains(&y) => {
+                shape.rows[y - fy] << x
+            }
+            _ => 0,
+        };
+        picture.push('|');
+        for column in 0..tower.chamber.width {
+            let bit = 1u64 << column;
+            picture.push(if moving & bit != 0 {
+                '@'
+            } else if settled & bit != 0 {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        picture.push_str("|\n");
+    }
+    if bottom == 0 {
+        picture.push_str(&format!("+{}+\n", "-".repeat(tower.chamber.width)));
+    }
+    picture
+}
+
+/// Drops `rock_count` rocks as part 1 does, showing whatever the chamber's
+/// view asks for along the way.
+pub fn watch(chamber: &Chamber, rock_count: usize) -> eyre::Result<()> {
+    let view = &chamber.view;
+    let step_rows = if view.rows > 0 { view.rows } else { STEP_ROWS };
+    let mut stepping = view.step;
+    let mut tower = Tower::new(chamber);
+    while tower.rock_number < rock_count {
+        if stepping {
+            // This is interactive, so it goes straight to the terminal.
+            let (x, y) = tower.spawn_point();
+            eprint!(
+                "Rock {} appears:\n{}Press enter to drop it, or q to stop stepping: ",
+                tower.rock_number + 1,
+                draw(&tower, step_rows, Some((tower.next_shape(), x, y)))
+            );
+            let mut answer = String::new();
+            if io::stdin().lock().read_line(&mut answer)? == 0 || answer.trim() == "q" {
+                stepping = false;
+            }
+        }
+        tower.drop_rock();
+    }
+    if view.rows > 0 {
+        diag!(
+            "The top of the tower after {rock_count} rocks:\n{}",
+            draw(&tower, view.rows, None)
+        );
+    }
+    if let Some(path) = &view.image {
+        let image = Image::of(&tower);
+        let bytes = if path.ends_with(".png") {
+            image.png()
+        } else {
+            image.ppm()
+        };
+        fs::write(path, bytes).map_err(|e| eyre!("Failed to write {path}: {e}"))?;
+        diag!("Wrote the tower to {path}");
+    }
+    Ok(())
+}
+
+// Each cell of the tower is a square this many pixels across.
+const SCALE: usize = 4;
+
+const AIR: [u8; 3] = [15, 15, 35];
+const ROCK: [u8; 3] = [204, 204, 204];
+const WALL: [u8; 3] = [102, 102, 102];
+
+// An RGB image, a row at a time from the top.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    // Draws every row of the tower that's still kept, with the walls and the
+    // floor beneath.
+    fn of(tower: &Tower) -> Image {
+        let mut cells: Vec<Vec<[u8; 3]>> = (tower.y_offset..tower.height)
+            .rev()
+            .map(|y| {
+                let row = tower.rows[y - tower.y_offset];
+                let rock =
+                    (0..tower.chamber.width)
+                        .map(|column| if row & 1 << column != 0 { ROCK } else { AIR });
+                iter::once(WALL)
+                    .chain(rock)
+                    .chain(iter::once(WALL))
+                    .collect()
+            })
+            .collect();
+        cells.push(vec![WALL; tower.chamber.width + 2]);
+
+        let width = (tower.chamber.width + 2) * SCALE;
+        let mut pixels = Vec::with_capacity(width * cells.len() * SCALE * 3);
+        for row in &cells {
+            for _ in 0..SCALE {
+                for cell in row {
+                    for _ in 0..SCALE {
+                        pixels.extend_from_slice(cell);
+                    }
+                }
+            }
+        }
+        Image {
+            width,
+            height: cells.len() * SCALE,
+            pixels,
+        }
+    }
+
+    fn ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend_from_slice(&self.pixels);
+        ppm
+    }
+
+    // PNG needs the pixels in a zlib stream, but that can be made of
+    // uncompressed blocks, which saves writing a compressor.
+    fn png(&self) -> Vec<u8> {
+        // Each row starts with its filter type, and 0 is none.
+        let mut raw = Vec::with_capacity(self.pixels.len() + self.height);
+        for row in self.pixels.chunks(self.width * 3) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(0xffff).peekable();
+        while let Some(block) = blocks.next() {
+            let len = block.len() as u16;
+            zlib.push(blocks.peek().is_none() as u8);
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, and the only compression, filtering and
+        // (no) interlacing methods there are.
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        push_chunk(&mut png, b"IHDR", &header);
+        push_chunk(&mut png, b"IDAT", &zlib);
+        push_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xedb88320
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in bytes {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solver;
+
+    use super::*;
+    use crate::Day17;
+
+    fn tower_after(chamber: &Chamber, rocks: usize) -> Tower<'_> {
+        let mut tower = Tower::new(chamber);
+        for _ in 0..rocks {
+            tower.drop_rock();
+        }
+        tower
+    }
+
+    #[test]
+    fn draws_the_top_of_the_tower() {
+        let chamber = Day17::parse(include_str!("../demo.txt")).unwrap();
+        let tower = tower_after(&chamber, 1);
+        assert_eq!(draw(&tower, 5, None), "|..####.|\n+-------+\n");
+        let (x, y) = tower.spawn_point();
+        assert_eq!(
+            draw(&tower, 10, Some((tower.next_shape(), x, y))),
+            "|...@...|\n|..@@@..|\n|...@...|\n|.......|\n|.......|\n|.......|\n|..####.|\n+-------+\n"
+        );
+        let tower = tower_after(&chamber, 2);
+        assert_eq!(draw(&tower, 2, None), "|...#...|\n|..###..|\n");
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    // Undoes the uncompressed blocks of a zlib stream, checking each header.
+    fn inflate_stored(mut zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(&zlib[..2], [0x78, 0x01]);
+        zlib = &zlib[2..];
+        let mut raw = Vec::new();
+        loop {
+            let last = zlib[0];
+            let len = u16::from_le_bytes([zlib[1], zlib[2]]);
+            assert_eq!(u16::from_le_bytes([zlib[3], zlib[4]]), !len);
+            raw.extend_from_slice(&zlib[5..5 + len as usize]);
+            zlib = &zlib[5 + len as usize..];
+            if last == 1 {
+                break;
+            }
+            assert_eq!((last, len), (0, 0xffff));
+        }
+        assert_eq!(zlib, adler32(&raw).to_be_bytes());
+        raw
+    }
+
+    // The type and data of each chunk, checking their lengths and CRCs.
+    fn chunks(mut png: &[u8]) -> Vec<(&[u8], &[u8])> {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        png = &png[8..];
+        let mut chunks = Vec::new();
+        while !png.is_empty() {
+            let len = u32::from_be_bytes(png[..4].try_into().unwrap()) as usize;
+            let crc = u32::from_be_bytes(png[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(&png[4..8 + len]));
+            chunks.push((&png[4..8], &png[8..8 + len]));
+            png = &png[12 + len..];
+        }
+        chunks
+    }
+
+    #[test]
+    fn png_of_a_tower() {
+        let chamber = Day17::parse(include_str!("../demo.txt")).unwrap();
+        let image = Image::of(&tower_after(&chamber, 1));
+        // One row of rock and the floor, with the walls either side.
+        assert_eq!((image.width, image.height), (36, 8));
+        let png = image.png();
+        let chunks = chunks(&png);
+        let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 36, 0, 0, 0, 8, 8, 2, 0, 0, 0]);
+        let raw = inflate_stored(chunks[1].1);
+        assert_eq!(raw.len(), 8 * (36 * 3 + 1));
+        // Each row is unfiltered, and the top left pixel is wall.
+        assert!(raw.chunks(36 * 3 + 1).all(|row| row[0] == 0));
+        assert_eq!(raw[1..4], WALL);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+
+    #[test]
+    fn png_splits_into_blocks() {
+        let image = Image {
+            width: 200,
+            height: 200,
+            pixels: (0..200 * 200 * 3).map(|i| i as u8).collect(),
+        };
+        let png = image.png();
+        let raw = inflate_stored(chunks(&png)[1].1);
+        let expected: Vec<u8> = image
+            .pixels
+            .chunks(200 * 3)
+            .flat_map(|row| std::iter::once(0).chain(row.iter().copied()))
+            .collect();
+        assert_eq!(raw, expected);
+    }
+}
@@ -0,0 +1,498 @@
+use std::{
+    collections::BTreeSet,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use aoc_common::{
+    diag,
+    parser::{lines, Line},
+    Param, Params, ParseError, Solver,
+};
+
+/// The robots a factory can build, each collecting the resource it's named
+/// after, and what each costs.
+#[derive(Debug)]
+pub struct Blueprint {
+    id: u32,
+    // Named in the order their robots are listed, and numbered by it.
+    resources: Vec<String>,
+    // What each robot costs, in each resource.
+    costs: Vec<Amounts>,
+    // The resource to collect as much of as possible, and the robot there is
+    // to start with.
+    goal: usize,
+    first_robot: usize,
+}
+
+// The search copies these around a lot, so they're kept off the heap with
+// room for this many resources.
+const MAX_RESOURCES: usize = 8;
+
+// An amount of each resource, or a number of each robot.
+type Amounts = [u32; MAX_RESOURCES];
+
+// A recipe as written, with where each name was given to point at any that
+// turn out to be wrong once the whole blueprint is read.
+struct Recipe<'a> {
+    robot: &'a str,
+    at: Line<'a>,
+    cost: Vec<(u32, &'a str, Line<'a>)>,
+}
+
+// Reads a recipe such as " Each obsidian robot costs 3 ore and 14 clay."
+fn read_recipe<'a>(line: &mut Line<'a>) -> Result<Recipe<'a>, ParseError> {
+    line.tag(" Each ")?;
+    let at = line.clone();
+    let robot = line.word()?;
+    line.tag(" robot costs ")?;
+    let mut cost = Vec::new();
+    loop {
+        let amount = line.number()?;
+        line.tag(" ")?;
+        let resource_at = line.clone();
+        cost.push((amount, line.word()?, resource_at));
+        if line.eat(".") {
+            return Ok(Recipe { robot, at, cost });
+        }
+        line.tag(" and ")?;
+    }
+}
+
+fn line_to_blueprint(
+    mut line: Line,
+    goal: &str,
+    first_robot: &str,
+) -> Result<Blueprint, ParseError> {
+    line.tag("Blueprint ")?;
+    let id = line.number()?;
+    line.tag(":")?;
+    let mut recipes: Vec<Recipe> = Vec::new();
+    while !line.is_empty() {
+        let recipe = read_recipe(&mut line)?;
+        let robot = recipe.robot;
+        if recipes.iter().any(|r| r.robot == robot) {
+            return Err(recipe
+                .at
+                .error(format!("there's already a recipe for {robot} robots")));
+        }
+        if recipes.len() == MAX_RESOURCES {
+            return Err(recipe
+                .at
+                .error(format!("there can be at most {MAX_RESOURCES} robots")));
+        }
+        recipes.push(recipe);
+    }
+    let resources: Vec<String> = recipes.iter().map(|r| r.robot.to_owned()).collect();
+    let find = |name: &str| resources.iter().position(|r| r == name);
+    let mut costs = Vec::new();
+    for recipe in &recipes {
+        let mut amounts = [0; MAX_RESOURCES];
+        for (amount, name, at) in &recipe.cost {
+            let resource = find(name)
+                .ok_or_else(|| at.error(format!("there's no {name} robot to collect it")))?;
+            amounts[resource] += amount;
+        }
+        costs.push(amounts);
+    }
+    let robot =
+        |name: &str| find(name).ok_or_else(|| line.error(format!("expected a {name} robot")));
+    Ok(Blueprint {
+        id,
+        goal: robot(goal)?,
+        first_robot: robot(first_robot)?,
+        resources,
+        costs,
+    })
+}
+
+struct Factory<'a> {
+    blueprint: &'a Blueprint,
+    minutes: u32,
+    // Only one robot can be built a minute, so there's no use having more of
+    // a robot than any recipe needs of what it collects.
+    max_useful: Amounts,
+    // The robots built so far on the way to the current state, and the best
+    // way found yet.
+    builds: Vec<Build>,
+    best: Outcome,
+    // Every state searched from, as the minutes left, resources and robots.
+    seen: BTreeSet<(u32, Amounts, Amounts)>,
+}
+
+// A robot, and the minute it was built in.
+type Build = (u32, usize);
+
+/// The most of its goal a blueprint can collect, the robots to build to
+/// collect it, and how much searching that took.
+#[derive(Clone, Debug, Default)]
+pub struct Outcome {
+    pub most: u32,
+    pub builds: Vec<(u32, String)>,
+    pub nodes: usize,
+    // States given up on because they couldn't beat the best, or had already
+    // been searched from.
+    pub bounded: usize,
+    pub repeated: usize,
+}
+
+impl<'a> Factory<'a> {
+    fn new(blueprint: &'a Blueprint, minutes: u32) -> Factory<'a> {
+        let mut max_useful = [0; MAX_RESOURCES];
+        for (resource, max) in max_useful.iter_mut().enumerate() {
+            *max = if resource == blueprint.goal {
+                u32::MAX
+            } else {
+                blueprint
+                    .costs
+                    .iter()
+                    .map(|c| c[resource])
+                    .max()
+                    .unwrap_or(0)
+            };
+        }
+        Factory {
+            blueprint,
+            minutes,
+            max_useful,
+            builds: Vec::new(),
+            best: Outcome::default(),
+            seen: BTreeSet::new(),
+        }
+    }
+
+    // How many minutes until there's enough to build `robot`, or `None` if
+    // something it needs isn't being collected.
+    fn wait_for(&self, robot: usize, resources: &Amounts, robots: &Amounts) -> Option<u32> {
+        let mut wait = 0;
+        for (resource, cost) in self.blueprint.costs[robot].iter().enumerate() {
+            if *cost > resources[resource] {
+                if robots[resource] == 0 {
+                    return None;
+                }
+                let needed = cost - resources[resource];
+                wait = wait.max(needed.div_ceil(robots[resource]));
+            }
+        }
+        Some(wait)
+    }
+
+    // Branches on which robot to build next, waiting until it can be afforded,
+    // keeping the best way found to the most of the goal at the end.
+    fn search(&mut self, resources: Amounts, robots: Amounts, minutes_left: u32) {
+        self.best.nodes += 1;
+        let (goal, count) = (self.blueprint.goal, self.blueprint.resources.len());
+        // Can always just build no more robots for the rest of the time.
+        let idle = resources[goal] + robots[goal] * minutes_left;
+        if idle > self.best.most {
+            self.best.most = idle;
+            self.best.builds = self.builds.iter().map(|b| self.name(b)).collect();
+        }
+        // At best another goal robot gets built every minute from now on.
+        let bound = idle + minutes_left * minutes_left.saturating_sub(1) / 2;
+        if bound <= self.best.most {
+            self.best.bounded += 1;
+            return;
+        }
+        if !self.seen.insert((minutes_left, resources, robots)) {
+            self.best.repeated += 1;
+            return;
+        }
+        // Later robots tend to be worth more, so trying them first finds good
+        // answers sooner, which makes the bound cut off more.
+        for robot in (0..count).rev() {
+            if robots[robot] >= self.max_useful[robot] {
+                continue;
+            }
+            let Some(wait) = self.wait_for(robot, &resources, &robots) else {
+                continue;
+            };
+            if wait >= minutes_left {
+                continue;
+            }
+            // Collect while waiting and building, then pay for the robot.
+            let minutes_left = minutes_left - wait - 1;
+            let mut resources = resources;
+            for (resource, have) in resources[..count].iter_mut().enumerate() {
+                *have =
+                    *have + robots[resource] * (wait + 1) - self.blueprint.costs[robot][resource];
+            }
+            let mut robots = robots;
+            robots[robot] += 1;
+            self.builds.push((self.minutes - minutes_left, robot));
+            self.search(resources, robots, minutes_left);
+            self.builds.pop();
+        }
+    }
+
+    fn name(&self, (minute, robot): &Build) -> (u32, String) {
+        (*minute, self.blueprint.resources[*robot].clone())
+    }
+}
+
+/// The most of its goal a blueprint can collect in the time given, starting
+/// with a single robot.
+pub fn most_collected(blueprint: &Blueprint, minutes: u32) -> Outcome {
+    let resources = [0; MAX_RESOURCES];
+    let mut robots = resources;
+    robots[blueprint.first_robot] = 1;
+    let mut factory = Factory::new(blueprint, minutes);
+    factory.search(resources, robots, minutes);
+    factory.best
+}
+
+// The most each blueprint can collect, worked out on a thread per core
+// with each taking the next blueprint as it finishes one.
+fn most_collected_each(factories: &Factories, count: usize, minutes: u32) -> Vec<u32> {
+    let blueprints = &factories.blueprints[..count.min(factories.blueprints.len())];
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(blueprints.len());
+    let next = AtomicUsize::new(0);
+    let mut outcomes = vec![Outcome::default(); blueprints.len()];
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(blueprint) = blueprints.get(idx) else {
+                            return done;
+                        };
+                        done.push((idx, most_collected(blueprint, minutes)));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            for (idx, outcome) in worker.join().expect("worker thread panicked") {
+                outcomes[idx] = outcome;
+            }
+        }
+    });
+    for (blueprint, outcome) in blueprints.iter().zip(&outcomes) {
+        diag!(
+            "Blueprint {} collects {} {} in {minutes} minutes, searching {} states \
+             ({} bounded, {} repeated)",
+            blueprint.id,
+            outcome.most,
+            blueprint.resources[blueprint.goal],
+            outcome.nodes,
+            outcome.bounded,
+            outcome.repeated
+        );
+        if factories.show_builds {
+            for (minute, robot) in &outcome.builds {
+                diag!("  Minute {minute}: build a robot collecting {robot}");
+            }
+        }
+    }
+    outcomes.into_iter().map(|o| o.most).collect()
+}
+
+pub struct Factories {
+    blueprints: Vec<Blueprint>,
+    show_builds: bool,
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Input = Factories;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "goal",
+            default: "geode",
+            help: "The resource to collect as much of as possible",
+        },
+        Param {
+            name: "first-robot",
+            default: "ore",
+            help: "The robot there is to start with",
+        },
+        Param {
+            name: "show-builds",
+            default: "false",
+            help: "Print the minute each robot is built in the best plan",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input> {
+        let goal: String = params.get("goal")?;
+        let first_robot: String = params.get("first-robot")?;
+        Ok(Factories {
+            blueprints: lines(input)
+                .map(|line| line_to_blueprint(line, &goal, &first_robot))
+                .collect::<Result<_, _>>()?,
+            show_builds: params.get("show-builds")?,
+        })
+    }
+
+    fn part1(factories: &Self::Input) -> eyre::Result<u32> {
+        Ok(factories
+            .blueprints
+            .iter()
+            .zip(most_collected_each(factories, usize::MAX, 24))
+            .map(|(blueprint, most)| blueprint.id * most)
+            .sum())
+    }
+
+    fn part2(factories: &Self::Input) -> eyre::Result<u32> {
+        // The elephants ate the rest of the blueprints.
+        Ok(most_collected_each(factories, 3, 32).into_iter().product())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEMO: &str = include_str!("../demo.txt");
+
+    const DIAMONDS: &str = "Blueprint 7: Each ore robot costs 2 ore. Each clay robot costs 2 ore. \
+        Each obsidian robot costs 2 ore and 3 clay. Each geode robot costs 2 ore and 3 obsidian. \
+        Each diamond robot costs 2 ore and 2 geode.";
+
+    fn parse_error(text: &str, goal: &str) -> ParseError {
+        line_to_blueprint(Line::new(1, text), goal, "ore").unwrap_err()
+    }
+
+    // Where `name` first appears after `after` in `text`, as a column.
+    fn column(text: &str, after: &str, name: &str) -> usize {
+        let start = text.find(after).unwrap();
+        start + text[start..].find(name).unwrap() + 1
+    }
+
+    #[test]
+    fn more_resources() {
+        let blueprint = line_to_blueprint(Line::new(1, DIAMONDS), "diamond", "ore").unwrap();
+        assert_eq!(blueprint.id, 7);
+        assert_eq!(
+            blueprint.resources,
+            ["ore", "clay", "obsidian", "geode", "diamond"]
+        );
+        assert_eq!((blueprint.goal, blueprint.first_robot), (4, 0));
+        assert_eq!(blueprint.costs[4][..5], [2, 0, 0, 2, 0]);
+        assert_eq!(blueprint.costs[2][..5], [2, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn bad_blueprints() {
+        let text = DEMO
+            .lines()
+            .next()
+            .unwrap()
+            .replace("7 obsidian", "7 diamond");
+        let err = parse_error(&text, "geode");
+        assert_eq!(err.column, column(&text, "geode robot", "diamond"));
+        assert_eq!(err.message, "there's no diamond robot to collect it");
+
+        let text = DEMO
+            .lines()
+            .next()
+            .unwrap()
+            .replace("Each clay robot", "Each ore robot");
+        let err = parse_error(&text, "geode");
+        assert_eq!(err.column, column(&text, "costs 4 ore", "ore robot"));
+        assert_eq!(err.message, "there's already a recipe for ore robots");
+
+        let err = parse_error(DEMO.lines().next().unwrap(), "diamond");
+        assert_eq!(err.message, "expected a diamond robot");
+    }
+
+    // Plays out the robots built minute by minute, checking each can be paid
+    // for, giving how much of the goal there is at the end.
+    fn replay(blueprint: &Blueprint, builds: &[(u32, String)], minutes: u32) -> u32 {
+        let mut resources: Amounts = [0; MAX_RESOURCES];
+        let mut robots: Amounts = [0; MAX_RESOURCES];
+        robots[blueprint.first_robot] = 1;
+        let mut builds = builds.iter().peekable();
+        for minute in 1..=minutes {
+            let built = builds.next_if(|(m, _)| *m == minute).map(|(_, name)| {
+                let robot = blueprint.resources.iter().position(|r| r == name).unwrap();
+                for (have, cost) in resources.iter_mut().zip(&blueprint.costs[robot]) {
+                    *have = have.checked_sub(*cost).expect("the robot can be paid for");
+                }
+                robot
+            });
+            for (have, robots) in resources.iter_mut().zip(&robots) {
+                *have += robots;
+            }
+            if let Some(robot) = built {
+                robots[robot] += 1;
+            }
+        }
+        assert_eq!(builds.next(), None, "every robot is built in time");
+        resources[blueprint.goal]
+    }
+
+    // The same search with no bound, no memo and no limit on robots.
+    fn unpruned(blueprint: &Blueprint, resources: Amounts, robots: Amounts, left: u32) -> u32 {
+        let factory = Factory::new(blueprint, 0);
+        let goal = blueprint.goal;
+        let mut best = resources[goal] + robots[goal] * left;
+        for robot in 0..blueprint.resources.len() {
+            let Some(wait) = factory.wait_for(robot, &resources, &robots) else {
+                continue;
+            };
+            if wait >= left {
+                continue;
+            }
+            let mut resources = resources;
+            for (resource, have) in resources.iter_mut().enumerate() {
+                *have = *have + robots[resource] * (wait + 1) - blueprint.costs[robot][resource];
+            }
+            let mut robots = robots;
+            robots[robot] += 1;
+            best = best.max(unpruned(blueprint, resources, robots, left - wait - 1));
+        }
+        best
+    }
+
+    #[test]
+    fn build_order() {
+        let factories = Day19::parse(DEMO).unwrap();
+        let blueprint = &factories.blueprints[0];
+        let outcome = most_collected(blueprint, 24);
+        assert_eq!(outcome.most, 9);
+        assert_eq!(replay(blueprint, &outcome.builds, 24), 9);
+        assert!(outcome.bounded > 0 && outcome.repeated > 0);
+    }
+
+    #[test]
+    fn pruning_keeps_the_best() {
+        // Collecting obsidian gets going sooner than geodes, so small cases
+        // still have something to find.
+        let mut blueprints: Vec<Blueprint> = lines(DEMO)
+            .map(|line| line_to_blueprint(line, "obsidian", "ore").unwrap())
+            .collect();
+        blueprints.push(line_to_blueprint(Line::new(1, DIAMONDS), "diamond", "ore").unwrap());
+        for blueprint in &blueprints {
+            for minutes in [12, 16] {
+                let mut robots = [0; MAX_RESOURCES];
+                robots[blueprint.first_robot] = 1;
+                let outcome = most_collected(blueprint, minutes);
+                assert_eq!(
+                    outcome.most,
+                    unpruned(blueprint, [0; MAX_RESOURCES], robots, minutes),
+                    "blueprint {} in {minutes} minutes",
+                    blueprint.id
+                );
+                assert_eq!(replay(blueprint, &outcome.builds, minutes), outcome.most);
+            }
+        }
+    }
+
+    #[test]
+    fn quality_levels() {
+        let factories = Day19::parse(DEMO).unwrap();
+        assert_eq!(factories.blueprints[1].id, 2);
+        // Blueprint 1 collects 9 geodes and blueprint 2 collects 12.
+        assert_eq!(Day19::part1(&factories).unwrap(), 9 + 2 * 12);
+        assert_eq!(most_collected_each(&factories, 1, 24), [9]);
+    }
+}
@@ -0,0 +1,125 @@
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Throw {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+// The second column of the strategy guide, which means something different in
+// each part.
+#[derive(Debug, Copy, Clone)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+fn line_to_throws(mut line: Line) -> Result<(Throw, Column), ParseError> {
+    let them = line.one_of(&[
+        ("A", Throw::Rock),
+        ("B", Throw::Paper),
+        ("C", Throw::Scissors),
+    ])?;
+    line.tag(" ")?;
+    let column = line.one_of(&[("X", Column::X), ("Y", Column::Y), ("Z", Column::Z)])?;
+    line.end()?;
+    Ok((them, column))
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum GameResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+fn get_our_move(game_result: GameResult, them: Throw) -> Throw {
+    match them {
+        Throw::Paper => match game_result {
+            GameResult::Loss => Throw::Rock,
+            GameResult::Draw => Throw::Paper,
+            GameResult::Win => Throw::Scissors,
+        },
+        Throw::Rock => match game_result {
+            GameResult::Loss => Throw::Scissors,
+            GameResult::Draw => Throw::Rock,
+            GameResult::Win => Throw::Paper,
+        },
+        Throw::Scissors => match game_result {
+            GameResult::Loss => Throw::Paper,
+            GameResult::Draw => Throw::Scissors,
+            GameResult::Win => Throw::Rock,
+        },
+    }
+}
+
+fn get_result(us: Throw, them: Throw) -> GameResult {
+    match (us, them) {
+        (Throw::Rock, Throw::Scissors)
+        | (Throw::Paper, Throw::Rock)
+        | (Throw::Scissors, Throw::Paper) => GameResult::Win,
+        (Throw::Rock, Throw::Rock)
+        | (Throw::Paper, Throw::Paper)
+        | (Throw::Scissors, Throw::Scissors) => GameResult::Draw,
+        _ => GameResult::Loss,
+    }
+}
+
+fn score(us: Throw, result: GameResult) -> i32 {
+    (match result {
+        GameResult::Win => 6,
+        GameResult::Draw => 3,
+        GameResult::Loss => 0,
+    }) + match us {
+        Throw::Rock => 1,
+        Throw::Paper => 2,
+        Throw::Scissors => 3,
+    }
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<(Throw, Column)>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Ok(lines(input).map(line_to_throws).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(rounds: &Self::Input) -> eyre::Result<i32> {
+        // The second column is the throw we should make.
+        Ok(rounds
+            .iter()
+            .map(|(them, column)| {
+                let us = match column {
+                    Column::X => Throw::Rock,
+                    Column::Y => Throw::Paper,
+                    Column::Z => Throw::Scissors,
+                };
+                score(us, get_result(us, *them))
+            })
+            .sum())
+    }
+
+    fn part2(rounds: &Self::Input) -> eyre::Result<i32> {
+        // The second column is how the round needs to end.
+        Ok(rounds
+            .iter()
+            .map(|(them, column)| {
+                let result = match column {
+                    Column::X => GameResult::Loss,
+                    Column::Y => GameResult::Draw,
+                    Column::Z => GameResult::Win,
+                };
+                score(get_our_move(result, *them), result)
+            })
+            .sum())
+    }
+}
@@ -0,0 +1,98 @@
+use aoc_common::{parser::lines, Params, Solver};
+use eyre::eyre;
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+fn grove_coordinates(numbers: &[i64], key: i64, rounds: usize) -> eyre::Result<i64> {
+    let mut numbers = numbers
+        .iter()
+        .enumerate()
+        .map(|(idx, n)| {
+            n.checked_mul(key)
+                .map(|n| (n, idx))
+                .ok_or_else(|| eyre!("{n} is too big to decrypt"))
+        })
+        .collect::<eyre::Result<Vec<(i64, usize)>>>()?;
+    let count = numbers.len();
+    for _ in 0..rounds {
+        for original_idx in 0..count {
+            let current_pos = numbers.iter().position(|&x| x.1 == original_idx).unwrap();
+            let value = numbers[current_pos].0;
+            // A lone number has nowhere to move to.
+            if value == 0 || count < 2 {
+                continue;
+            }
+            numbers.remove(current_pos);
+            let count = numbers.len() as i64;
+            let new_pos = ((current_pos as i64 + value.rem_euclid(count)) % count) as usize;
+            numbers.insert(new_pos, (value, original_idx));
+        }
+    }
+    let zero_idx = numbers
+        .iter()
+        .position(|&x| x.0 == 0)
+        .ok_or_else(|| eyre!("No zero in the file"))?;
+    let idx1 = (zero_idx + 1000) % numbers.len();
+    let idx2 = (zero_idx + 2000) % numbers.len();
+    let idx3 = (zero_idx + 3000) % numbers.len();
+    numbers[idx1]
+        .0
+        .checked_add(numbers[idx2].0)
+        .and_then(|sum| sum.checked_add(numbers[idx3].0))
+        .ok_or_else(|| eyre!("The grove coordinates are too big to add up"))
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        let mut numbers = Vec::new();
+        for mut line in lines(input) {
+            numbers.push(line.number()?);
+            line.end()?;
+        }
+        Ok(numbers)
+    }
+
+    fn part1(numbers: &Self::Input) -> eyre::Result<i64> {
+        grove_coordinates(numbers, 1, 1)
+    }
+
+    fn part2(numbers: &Self::Input) -> eyre::Result<i64> {
+        grove_coordinates(numbers, DECRYPTION_KEY, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_files() {
+        assert_eq!(grove_coordinates(&[0], 1, 1).unwrap(), 0);
+        let err = grove_coordinates(&[5], 1, 1).unwrap_err();
+        assert_eq!(err.to_string(), "No zero in the file");
+        assert_eq!(grove_coordinates(&[3, 0], 1, 1).unwrap(), 0);
+        assert!(grove_coordinates(&[], 1, 1).is_err());
+    }
+
+    #[test]
+    fn overflow() {
+        let numbers = Day20::parse("9223372036854775807\n0\n").unwrap();
+        let err = Day20::part2(&numbers).unwrap_err();
+        assert_eq!(err.to_string(), "9223372036854775807 is too big to decrypt");
+        let err = grove_coordinates(&[i64::MAX, i64::MAX, 0], 1, 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The grove coordinates are too big to add up"
+        );
+        assert_eq!(
+            grove_coordinates(&[i64::MIN, 0, 1], 1, 1).unwrap(),
+            i64::MIN + 1
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use aoc_common::{
+    diag,
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+use eyre::eyre;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub mod rational;
+
+use rational::Rational;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Op {
+    fn apply(self, lhs: Rational, rhs: Rational) -> Option<Rational> {
+        match self {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Subtract => lhs.checked_sub(rhs),
+            Op::Multiply => lhs.checked_mul(rhs),
+            Op::Divide => lhs.checked_div(rhs),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Monkey {
+    Number(i64),
+    Job(Op, String, String),
+}
+
+// Reads a monkey's job. The monkeys it waits on are returned too, along with
+// where they were named.
+fn parse_ln<'a>(
+    mut line: Line<'a>,
+    operands: &mut Vec<Line<'a>>,
+) -> Result<(String, Monkey), ParseError> {
+    let name = line.word()?.to_owned();
+    line.tag(": ")?;
+    if line
+        .rest()
+        .starts_with(|c: char| c.is_ascii_digit() || c == '-')
+    {
+        let value = line.number()?;
+        line.end()?;
+        return Ok((name, Monkey::Number(value)));
+    }
+    operands.push(line.clone());
+    let lhs = line.word()?.to_owned();
+    let op = line.one_of(&[
+        (" + ", Op::Add),
+        (" - ", Op::Subtract),
+        (" * ", Op::Multiply),
+        (" / ", Op::Divide),
+    ])?;
+    operands.push(line.clone());
+    let rhs = line.word()?.to_owned();
+    line.end()?;
+    Ok((name, Monkey::Job(op, lhs, rhs)))
+}
+
+// A monkey with the jobs of every monkey it waits on beneath it. Everything
+// is worked out exactly, so no number is ever rounded.
+#[derive(Debug)]
+struct Node {
+    name: String,
+    job: Job,
+    // Whether humn is anywhere beneath.
+    has_human: bool,
+}
+
+#[derive(Debug)]
+enum Job {
+    Number(Rational),
+    Human,
+    Op(Op, Box<Node>, Box<Node>),
+}
+
+// Builds the tree under `name`. The monkeys above it are kept in `waiting` to
+// catch any monkey that ends up waiting on itself.
+fn build<'a>(
+    monkeys: &'a BTreeMap<String, Monkey>,
+    name: &'a str,
+    waiting: &mut BTreeSet<&'a str>,
+) -> eyre::Result<Node> {
+    if !waiting.insert(name) {
+        return Err(eyre!("Monkey {name} ends up waiting on itself"));
+    }
+    let (job, has_human) = match &monkeys[name] {
+        _ if name == "humn" => (Job::Human, true),
+        Monkey::Number(value) => (Job::Number((*value).into()), false),
+        Monkey::Job(op, lhs, rhs) => {
+            let lhs = build(monkeys, lhs, waiting)?;
+            let rhs = build(monkeys, rhs, waiting)?;
+            let has_human = lhs.has_human || rhs.has_human;
+            (Job::Op(*op, Box::new(lhs), Box::new(rhs)), has_human)
+        }
+    };
+    waiting.remove(name);
+    Ok(Node {
+        name: name.to_owned(),
+        job,
+        has_human,
+    })
+}
+
+impl Node {
+    // What this monkey yells when humn yells `humn`. Monkeys only ever yell
+    // whole numbers, so a division that doesn't come out exactly is an error.
+    fn value(&self, humn: Rational) -> eyre::Result<Rational> {
+        let (op, lhs, rhs) = match &self.job {
+            Job::Number(value) => return Ok(*value),
+            Job::Human => return Ok(humn),
+            Job::Op(op, lhs, rhs) => (*op, lhs, rhs),
+        };
+        let (lhs, rhs) = (lhs.value(humn)?, rhs.value(humn)?);
+        if op == Op::Divide && rhs.is_zero() {
+            return Err(eyre!("Monkey {} divides {lhs} by zero", self.name));
+        }
+        let value = op
+            .apply(lhs, rhs)
+            .ok_or_else(|| eyre!("Monkey {}'s number overflows", self.name))?;
+        if value.integer().is_none() {
+            return Err(eyre!(
+                "Monkey {} can't divide {lhs} by {rhs} exactly",
+                self.name
+            ));
+        }
+        Ok(value)
+    }
+
+    // Works out what humn must yell for this monkey to yell `target`, undoing
+    // each job on the way down to humn.
+    fn solve(&self, mut target: Rational) -> eyre::Result<Rational> {
+        let mut node = self;
+        loop {
+            if target.integer().is_none() {
+                return Err(eyre!(
+                    "Monkey {} would have to yell {target}, which isn't a whole number",
+                    node.name
+                ));
+            }
+            let (op, lhs, rhs) = match &node.job {
+                Job::Human => return Ok(target),
+                Job::Number(_) => unreachable!("only monkeys waiting on humn are solved"),
+                Job::Op(op, lhs, rhs) => (*op, lhs, rhs),
+            };
+            if lhs.has_human && rhs.has_human {
+                return Err(eyre!(
+                    "humn appears on both sides of monkey {}'s job",
+                    node.name
+                ));
+            }
+            let name = &node.name;
+            let unknown = if lhs.has_human { lhs } else { rhs };
+            // The side without humn doesn't care what humn yells.
+            let known = if lhs.has_human { rhs } else { lhs }.value(Rational::from(0))?;
+            let zero_error = || eyre!("Monkey {name}'s job can't be undone as it involves zero");
+            target = match op {
+                Op::Add => target.checked_sub(known),
+                Op::Subtract if lhs.has_human => target.checked_add(known),
+                Op::Subtract => known.checked_sub(target),
+                Op::Multiply if known.is_zero() => return Err(zero_error()),
+                Op::Multiply => target.checked_div(known),
+                Op::Divide if lhs.has_human && known.is_zero() => {
+                    return Err(eyre!("Monkey {name} divides by zero"))
+                }
+                Op::Divide if lhs.has_human => target.checked_mul(known),
+                Op::Divide if target.is_zero() => return Err(zero_error()),
+                Op::Divide => known.checked_div(target),
+            }
+            .ok_or_else(|| eyre!("Working back through monkey {name} overflows"))?;
+            node = unknown;
+        }
+    }
+}
+
+pub struct Monkeys {
+    root: Node,
+    humn: Rational,
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Input = Monkeys;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Monkeys> {
+        let mut monkeys = BTreeMap::new();
+        let mut operands = Vec::new();
+        let mut last_line = 0;
+        for line in lines(input) {
+            last_line = line.line_number();
+            let (name, monkey) = parse_ln(line, &mut operands)?;
+            monkeys.insert(name, monkey);
+        }
+        for mut at in operands {
+            let name = at.word()?;
+            if !monkeys.contains_key(name) {
+                return Err(at.error(format!("there is no monkey {name}")).into());
+            }
+        }
+        let humn = match monkeys.get("humn") {
+            Some(Monkey::Number(value)) => Rational::from(*value),
+            _ => return Err(ParseError::end_of_input(last_line + 1, "a number for humn").into()),
+        };
+        if !monkeys.contains_key("root") {
+            return Err(ParseError::end_of_input(last_line + 1, "a job for root").into());
+        }
+        Ok(Monkeys {
+            root: build(&monkeys, "root", &mut BTreeSet::new())?,
+            humn,
+        })
+    }
+
+    fn part1(monkeys: &Monkeys) -> eyre::Result<i64> {
+        let value = monkeys.root.value(monkeys.humn)?;
+        value
+            .integer()
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| eyre!("Root's number {value} doesn't fit in 64 bits"))
+    }
+
+    fn part2(monkeys: &Monkeys) -> eyre::Result<i64> {
+        // Root really checks its two operands match, so whatever the side
+        // without humn comes to, the side with humn has to come to as well.
+        let Job::Op(_, lhs, rhs) = &monkeys.root.job else {
+            return Err(eyre!("Root needs to wait on two monkeys to compare them"));
+        };
+        let (unknown, known) = match (lhs.has_human, rhs.has_human) {
+            (true, false) => (lhs, rhs),
+            (false, true) => (rhs, lhs),
+            (true, true) => return Err(eyre!("humn appears on both sides of root's job")),
+            (false, false) => return Err(eyre!("Neither side of root's job depends on humn")),
+        };
+        let target = known.value(monkeys.humn)?;
+        diag!(
+            "{} has to yell {target} to match {}",
+            unknown.name,
+            known.name
+        );
+        let humn = unknown.solve(target)?;
+        humn.integer()
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| eyre!("humn's number {humn} doesn't fit in 64 bits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part2_error(input: &str) -> String {
+        let monkeys = Day21::parse(input).unwrap();
+        Day21::part2(&monkeys).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn humn_on_both_sides() {
+        assert_eq!(
+            part2_error("root: pppw + sjmn\npppw: humn * humn\nsjmn: 4\nhumn: 5\n"),
+            "humn appears on both sides of monkey pppw's job"
+        );
+        assert_eq!(
+            part2_error("root: humn + pppw\npppw: humn * two\ntwo: 2\nhumn: 5\n"),
+            "humn appears on both sides of root's job"
+        );
+    }
+
+    #[test]
+    fn inexact_division() {
+        assert_eq!(
+            part2_error("root: pppw + sjmn\npppw: humn * two\ntwo: 2\nsjmn: 5\nhumn: 1\n"),
+            "Monkey humn would have to yell 5/2, which isn't a whole number"
+        );
+    }
+
+    #[test]
+    fn zero_multiplier() {
+        assert_eq!(
+            part2_error("root: pppw + sjmn\npppw: humn * zero\nzero: 0\nsjmn: 5\nhumn: 1\n"),
+            "Monkey pppw's job can't be undone as it involves zero"
+        );
+        assert_eq!(
+            part2_error("root: pppw + sjmn\npppw: humn / zero\nzero: 0\nsjmn: 5\nhumn: 1\n"),
+            "Monkey pppw divides by zero"
+        );
+    }
+}
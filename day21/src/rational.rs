@@ -0,0 +1,149 @@
+//! Exact fractions, so that working backwards through the monkeys' sums never
+//! rounds.
+
+use std::fmt::{self, Display};
+
+/// A fraction of `i128`s, always kept in lowest terms with a positive
+/// denominator. Arithmetic is checked, giving `None` on overflow or division
+/// by zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    // Only gcd(i128::MIN, 0) or gcd(i128::MIN, i128::MIN) doesn't fit, and
+    // those are never needed.
+    a as i128
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Option<Rational> {
+        if den == 0 {
+            return None;
+        }
+        let divisor = gcd(num, den);
+        let (num, den) = (num / divisor, den / divisor);
+        if den < 0 {
+            Some(Rational {
+                num: num.checked_neg()?,
+                den: den.checked_neg()?,
+            })
+        } else {
+            Some(Rational { num, den })
+        }
+    }
+
+    pub fn integer(&self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        // Scale by as little as possible to keep clear of overflow.
+        let divisor = gcd(self.den, other.den);
+        let num = self
+            .num
+            .checked_mul(other.den / divisor)?
+            .checked_add(other.num.checked_mul(self.den / divisor)?)?;
+        Rational::new(num, (self.den / divisor).checked_mul(other.den)?)
+    }
+
+    pub fn checked_neg(self) -> Option<Rational> {
+        Some(Rational {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // Cancel across first to keep clear of overflow.
+        let a = gcd(self.num, other.den);
+        let b = gcd(other.num, self.den);
+        Rational::new(
+            (self.num / a).checked_mul(other.num / b)?,
+            (self.den / b).checked_mul(other.den / a)?,
+        )
+    }
+
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        self.checked_mul(Rational::new(other.den, other.num)?)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational {
+            num: n.into(),
+            den: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den).unwrap()
+    }
+
+    #[test]
+    fn new_normalises() {
+        assert_eq!(r(2, -4), r(-1, 2));
+        assert_eq!(r(-2, -4), r(1, 2));
+        assert_eq!(r(2, -4).to_string(), "-1/2");
+        assert_eq!(r(0, -5).integer(), Some(0));
+        assert_eq!(r(6, 3).integer(), Some(2));
+        assert_eq!(Rational::new(1, 0), None);
+        // Its denominator can't be made positive.
+        assert_eq!(Rational::new(i128::MIN, -1), None);
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(r(1, 2).checked_add(r(1, 3)), Some(r(5, 6)));
+        assert_eq!(r(1, 6).checked_add(r(1, 3)), Some(r(1, 2)));
+        assert_eq!(r(1, 2).checked_sub(r(1, 2)), Some(r(0, 1)));
+        assert_eq!(r(i128::MAX, 1).checked_add(r(1, 1)), None);
+    }
+
+    #[test]
+    fn mul() {
+        assert_eq!(r(2, 3).checked_mul(r(3, 4)), Some(r(1, 2)));
+        assert_eq!(r(-2, 3).checked_mul(r(-3, 2)), Some(r(1, 1)));
+        // Cancelling first keeps this in range.
+        assert_eq!(r(i128::MAX, 2).checked_mul(r(2, i128::MAX)), Some(r(1, 1)));
+        assert_eq!(r(i128::MAX, 1).checked_mul(r(2, 1)), None);
+    }
+
+    #[test]
+    fn div() {
+        assert_eq!(r(1, 2).checked_div(r(1, 4)), Some(r(2, 1)));
+        assert_eq!(r(1, 2).checked_div(r(-1, 4)), Some(r(-2, 1)));
+        assert_eq!(r(3, 1).checked_div(r(2, 1)).unwrap().to_string(), "3/2");
+        assert_eq!(r(1, 2).checked_div(r(0, 1)), None);
+    }
+}
@@ -0,0 +1,386 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use aoc_common::{
+    grid::Pos,
+    parser::{lines, Line},
+    Grid, Params, ParseError, Solver,
+};
+use eyre::eyre;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    // Off the edge of the map.
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Instruction {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+pub struct Notes {
+    map: Grid<Tile>,
+    path: Vec<Instruction>,
+}
+
+// Indexed by facing, which is also how it's scored: right, down, left, up.
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+fn parse_path(mut line: Line) -> Result<Vec<Instruction>, ParseError> {
+    let mut path = Vec::new();
+    while !line.is_empty() {
+        if line.rest().starts_with(|c: char| c.is_ascii_digit()) {
+            path.push(Instruction::Forward(line.number()?));
+        } else {
+            path.push(line.one_of(&[("L", Instruction::Left), ("R", Instruction::Right)])?);
+        }
+    }
+    Ok(path)
+}
+
+// A point or direction in 3D, used to fold the map into a cube.
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vec3, by: i32) -> Vec3 {
+    [v[0] * by, v[1] * by, v[2] * by]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+// Where a face of the cube ended up once folded: the direction it faces out,
+// and the directions its map right and down point.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    // Top left corner of the face on the map.
+    origin: Pos,
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    // The 3D direction of travel when moving with `facing` on this face.
+    fn axis(&self, facing: usize) -> Vec3 {
+        [self.right, self.down, neg(self.right), neg(self.down)][facing]
+    }
+
+    // Turns this face's frame over the edge in direction `facing`, giving the
+    // frame of the face joined on there.
+    fn fold(&self, facing: usize, origin: Pos) -> Face {
+        let axis = self.axis(facing);
+        let (right, down) = match facing {
+            0 | 2 => (
+                scale(self.normal, if facing == 0 { -1 } else { 1 }),
+                self.down,
+            ),
+            _ => (
+                self.right,
+                scale(self.normal, if facing == 1 { -1 } else { 1 }),
+            ),
+        };
+        Face {
+            origin,
+            normal: axis,
+            right,
+            down,
+        }
+    }
+}
+
+// The map folded up into a cube. Points are measured from the cube's centre in
+// half cells, so each cell's centre has whole coordinates.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    fn fold(map: &Grid<Tile>) -> eyre::Result<Cube> {
+        let area = map.iter().filter(|(_, t)| **t != Tile::Void).count();
+        let size = (1..).find(|s| s * s * 6 >= area).unwrap_or(0);
+        if size == 0 || size * size * 6 != area {
+            return Err(eyre!("A map of {area} tiles can't fold into a cube"));
+        }
+        // Find the faces laid out on the map, then fold them up starting from
+        // the first, walking between neighbours on the map.
+        let mut unfolded = BTreeMap::new();
+        for y in (0..map.height()).step_by(size) {
+            for x in (0..map.width()).step_by(size) {
+                if map[(x, y)] != Tile::Void {
+                    unfolded.insert((x / size, y / size), (x, y));
+                }
+            }
+        }
+        if unfolded.len() != 6 {
+            return Err(eyre!(
+                "Expected 6 faces of size {size}, found {}",
+                unfolded.len()
+            ));
+        }
+        // Six full faces cover the whole map, so no tile is left off them.
+        for (x, y) in unfolded.values() {
+            let full = (0..size).all(|j| {
+                (0..size).all(|i| map.get((x + i, y + j)).is_some_and(|t| *t != Tile::Void))
+            });
+            if !full {
+                return Err(eyre!(
+                    "The face with its corner at {:?} isn't a full {size} by {size} square",
+                    (x, y)
+                ));
+            }
+        }
+        let (first, origin) = unfolded.pop_first().expect("six faces");
+        let mut faces = vec![Face {
+            origin,
+            normal: [0, 0, -1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut to_visit = VecDeque::from([(first, faces[0])]);
+        while let Some(((tx, ty), face)) = to_visit.pop_front() {
+            for (facing, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+                let (Some(nx), Some(ny)) = (tx.checked_add_signed(*dx), ty.checked_add_signed(*dy))
+                else {
+                    continue;
+                };
+                if let Some(origin) = unfolded.remove(&(nx, ny)) {
+                    let next = face.fold(facing, origin);
+                    faces.push(next);
+                    to_visit.push_back(((nx, ny), next));
+                }
+            }
+        }
+        if !unfolded.is_empty() {
+            return Err(eyre!("The faces of the map aren't all joined up"));
+        }
+        for (idx, face) in faces.iter().enumerate() {
+            if faces[..idx].iter().any(|f| f.normal == face.normal) {
+                return Err(eyre!("Two faces of the map fold onto the same side"));
+            }
+        }
+        Ok(Cube { size, faces })
+    }
+
+    fn face_at(&self, (x, y): Pos) -> Option<&Face> {
+        self.faces.iter().find(|f| {
+            (f.origin.0..f.origin.0 + self.size).contains(&x)
+                && (f.origin.1..f.origin.1 + self.size).contains(&y)
+        })
+    }
+
+    // Moves over the edge of the cube from `pos`, returning where that lands
+    // on the map and which way we're facing there.
+    fn wrap(&self, pos: Pos, facing: usize) -> eyre::Result<(Pos, usize)> {
+        let n = self.size as i32;
+        let from = self
+            .face_at(pos)
+            .ok_or_else(|| eyre!("{pos:?} isn't on any face of the cube"))?;
+        let (i, j) = (
+            (pos.0 - from.origin.0) as i32,
+            (pos.1 - from.origin.1) as i32,
+        );
+        let point = add(
+            scale(from.normal, n),
+            add(
+                scale(from.right, 2 * i + 1 - n),
+                scale(from.down, 2 * j + 1 - n),
+            ),
+        );
+        // Step one half cell over the edge and one half cell down the side.
+        let axis = from.axis(facing);
+        let point = add(point, add(axis, neg(from.normal)));
+        let to = self
+            .faces
+            .iter()
+            .find(|f| f.normal == axis)
+            .ok_or_else(|| eyre!("No face of the cube is past {pos:?}"))?;
+        let (i, j) = (
+            (dot(point, to.right) + n - 1) / 2,
+            (dot(point, to.down) + n - 1) / 2,
+        );
+        let heading = neg(from.normal);
+        let facing = (0..4)
+            .find(|f| to.axis(*f) == heading)
+            .ok_or_else(|| eyre!("Going over the edge from {pos:?} doesn't run along a face"))?;
+        Ok(((to.origin.0 + i as usize, to.origin.1 + j as usize), facing))
+    }
+}
+
+enum Wrapping {
+    // Walking off an edge comes back on the opposite side of the map.
+    Flat,
+    Cube(Cube),
+}
+
+fn step(
+    map: &Grid<Tile>,
+    wrapping: &Wrapping,
+    pos: Pos,
+    facing: usize,
+) -> eyre::Result<(Pos, usize)> {
+    let delta = DIRECTIONS[facing];
+    match map.step(pos, delta) {
+        Some(next) if map[next] != Tile::Void => Ok((next, facing)),
+        _ => match wrapping {
+            Wrapping::Flat => {
+                // Walk back to the far side of the map.
+                let back = DIRECTIONS[(facing + 2) % 4];
+                let mut next = pos;
+                while let Some(p) = map.step(next, back).filter(|p| map[*p] != Tile::Void) {
+                    next = p;
+                }
+                Ok((next, facing))
+            }
+            Wrapping::Cube(cube) => cube.wrap(pos, facing),
+        },
+    }
+}
+
+fn password(notes: &Notes, wrapping: &Wrapping) -> eyre::Result<usize> {
+    let map = &notes.map;
+    let start = (0..map.width())
+        .find(|x| map[(*x, 0)] == Tile::Open)
+        .ok_or_else(|| eyre!("No open tile on the top row to start from"))?;
+    let (mut pos, mut facing) = ((start, 0), 0);
+    for instruction in &notes.path {
+        match instruction {
+            Instruction::Left => facing = (facing + 3) % 4,
+            Instruction::Right => facing = (facing + 1) % 4,
+            Instruction::Forward(count) => {
+                for _ in 0..*count {
+                    let (next, next_facing) = step(map, wrapping, pos, facing)?;
+                    if map[next] == Tile::Wall {
+                        break;
+                    }
+                    (pos, facing) = (next, next_facing);
+                }
+            }
+        }
+    }
+    Ok(1000 * (pos.1 + 1) + 4 * (pos.0 + 1) + facing)
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input = Notes;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Notes> {
+        let rows = input.lines().take_while(|l| !l.is_empty()).count();
+        let map_text: String = input.lines().take(rows).flat_map(|l| [l, "\n"]).collect();
+        let map = Grid::parse_padded(&map_text, ' ', |c| match c {
+            ' ' => Ok(Tile::Void),
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(eyre!("expected ' ', '.' or '#', found {c:?}")),
+        })?;
+        let path = lines(input)
+            .nth(rows + 1)
+            .ok_or_else(|| ParseError::end_of_input(rows + 2, "a path"))?;
+        Ok(Notes {
+            map,
+            path: parse_path(path)?,
+        })
+    }
+
+    fn part1(notes: &Notes) -> eyre::Result<usize> {
+        password(notes, &Wrapping::Flat)
+    }
+
+    fn part2(notes: &Notes) -> eyre::Result<usize> {
+        password(notes, &Wrapping::Cube(Cube::fold(&notes.map)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A net with each letter in `layout` a face of open tiles.
+    fn net(layout: &[&str], size: usize) -> Grid<Tile> {
+        let mut text = String::new();
+        for row in layout {
+            let line: String = row
+                .chars()
+                .flat_map(|c| std::iter::repeat_n(if c == ' ' { ' ' } else { '.' }, size))
+                .collect();
+            for _ in 0..size {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        text.push_str("\nL1\n");
+        Day22::parse(&text).unwrap().map
+    }
+
+    // Going back over the edge undoes every wrap.
+    fn check_wraps_back(map: &Grid<Tile>, cube: &Cube) {
+        for (pos, tile) in map.iter() {
+            if *tile == Tile::Void {
+                continue;
+            }
+            for (facing, delta) in DIRECTIONS.iter().enumerate() {
+                if map.step(pos, *delta).is_some_and(|p| map[p] != Tile::Void) {
+                    continue;
+                }
+                let (to, to_facing) = cube.wrap(pos, facing).unwrap();
+                assert_ne!(map[to], Tile::Void);
+                assert_eq!(
+                    cube.wrap(to, (to_facing + 2) % 4).unwrap(),
+                    (pos, (facing + 2) % 4),
+                    "wrapping from {pos:?} facing {facing}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn demo_wraps() {
+        let notes = Day22::parse(include_str!("../demo.txt")).unwrap();
+        let cube = Cube::fold(&notes.map).unwrap();
+        assert_eq!(cube.size, 4);
+        assert_eq!(cube.wrap((11, 5), 0).unwrap(), ((14, 8), 1));
+        assert_eq!(cube.wrap((10, 11), 1).unwrap(), ((1, 7), 3));
+        check_wraps_back(&notes.map, &cube);
+    }
+
+    #[test]
+    fn input_layout_wraps() {
+        let map = net(&[" AB", " C ", "DE ", "F  "], 4);
+        let cube = Cube::fold(&map).unwrap();
+        assert_eq!(cube.wrap((5, 0), 3).unwrap(), ((0, 13), 0));
+        assert_eq!(cube.wrap((9, 0), 3).unwrap(), ((1, 15), 3));
+        assert_eq!(cube.wrap((11, 1), 0).unwrap(), ((7, 10), 2));
+        check_wraps_back(&map, &cube);
+    }
+
+    #[test]
+    fn misaligned_net() {
+        let notes =
+            Day22::parse("   ..\n   ..\n......\n......\n    ....\n    ....\n\nL1\n").unwrap();
+        let err = Day22::part2(&notes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The face with its corner at (4, 0) isn't a full 2 by 2 square"
+        );
+        let err = Cube::fold(&net(&["AB", "C "], 2)).unwrap_err();
+        assert_eq!(err.to_string(), "A map of 12 tiles can't fold into a cube");
+    }
+}
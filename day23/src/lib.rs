@@ -0,0 +1,140 @@
+use aoc_common::{
+    diag,
+    grid::{signed_neighbours8, SignedPos},
+    Grid, Param, Params, Solver, SparseGrid,
+};
+
+pub struct Grove {
+    elves: SparseGrid<()>,
+    rounds: usize,
+    dump_after: usize,
+}
+
+// The squares an elf checks before proposing a move, with the one it moves to
+// first. They're tried in this order, starting one further on each round.
+const PROPOSALS: [[(i64, i64); 3]; 4] = [
+    // North
+    [(0, -1), (-1, -1), (1, -1)],
+    // South
+    [(0, 1), (-1, 1), (1, 1)],
+    // West
+    [(-1, 0), (-1, -1), (-1, 1)],
+    // East
+    [(1, 0), (1, -1), (1, 1)],
+];
+
+fn propose(elves: &SparseGrid<()>, (x, y): SignedPos, round: usize) -> Option<SignedPos> {
+    if !signed_neighbours8((x, y)).any(|p| elves.contains(p)) {
+        return None;
+    }
+    (0..PROPOSALS.len())
+        .map(|k| PROPOSALS[(round + k) % PROPOSALS.len()])
+        .find(|checks| {
+            checks
+                .iter()
+                .all(|(dx, dy)| !elves.contains((x + dx, y + dy)))
+        })
+        .map(|checks| (x + checks[0].0, y + checks[0].1))
+}
+
+// Plays one round, counted from 0, returning whether any elf moved.
+fn play_round(elves: &mut SparseGrid<()>, round: usize) -> bool {
+    let moves: Vec<(SignedPos, SignedPos)> = elves
+        .positions()
+        .filter_map(|elf| Some((elf, propose(elves, elf, round)?)))
+        .collect();
+    let mut proposed = SparseGrid::<usize>::new();
+    for (_, to) in &moves {
+        let count = proposed.get(*to).copied().unwrap_or(0);
+        proposed.insert(*to, count + 1);
+    }
+    let mut moved = false;
+    for (from, to) in moves {
+        // Elves that propose the same square all stay put.
+        if proposed.get(to) == Some(&1) {
+            elves.remove(from);
+            elves.insert(to, ());
+            moved = true;
+        }
+    }
+    moved
+}
+
+fn render(elves: &SparseGrid<()>) -> String {
+    elves.render('.', |_| '#')
+}
+
+// Plays rounds until `stop` says to, given the number of rounds played and
+// whether anything moved in the last. Returns the number of rounds played.
+fn spread(
+    grove: &Grove,
+    elves: &mut SparseGrid<()>,
+    mut stop: impl FnMut(usize, bool) -> bool,
+) -> usize {
+    let mut round = 0;
+    loop {
+        let moved = play_round(elves, round);
+        round += 1;
+        if round == grove.dump_after {
+            diag!("After round {round}:\n{}", render(elves));
+        }
+        if stop(round, moved) {
+            return round;
+        }
+    }
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Input = Grove;
+    type Part1 = i64;
+    type Part2 = usize;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rounds",
+            default: "10",
+            help: "Rounds to play before measuring the empty ground in part 1",
+        },
+        Param {
+            name: "dump-after",
+            default: "0",
+            help: "Round after which to print the elves, or 0 for none",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input> {
+        let map = Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(eyre::eyre!("expected '#' or '.', found {c:?}")),
+        })?;
+        let elves = map
+            .iter()
+            .filter(|(_, elf)| **elf)
+            .map(|((x, y), _)| ((x as i64, y as i64), ()))
+            .collect();
+        Ok(Grove {
+            elves,
+            rounds: params.get("rounds")?,
+            dump_after: params.get("dump-after")?,
+        })
+    }
+
+    fn part1(grove: &Self::Input) -> eyre::Result<i64> {
+        let mut elves = grove.elves.clone();
+        if grove.rounds > 0 {
+            spread(grove, &mut elves, |round, _| round == grove.rounds);
+        }
+        let Some(((min_x, min_y), (max_x, max_y))) = elves.bounds() else {
+            return Err(eyre::eyre!("There are no elves"));
+        };
+        Ok((max_x - min_x + 1) * (max_y - min_y + 1) - elves.len() as i64)
+    }
+
+    fn part2(grove: &Self::Input) -> eyre::Result<usize> {
+        let mut elves = grove.elves.clone();
+        Ok(spread(grove, &mut elves, |_, moved| !moved))
+    }
+}
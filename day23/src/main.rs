@@ -1,11 +1,8 @@
-use std::io::{self, BufRead};
+use day23::Day23;
 
 fn main() -> eyre::Result<()> {
-    let sum = 0;
-    for line in io::stdin().lock().lines() {
-        let line = line?;
-        print!("{}", line);
-    }
-    println!("Sum: {}", sum);
-    Ok(())
+    aoc_common::cli::run::<Day23>(
+        env!("CARGO_MANIFEST_DIR"),
+        &["Empty ground tiles", "First round with no moves"],
+    )
 }
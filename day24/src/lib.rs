@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use aoc_common::{diag, grid::Pos, Grid, Param, Params, ParseError, Solver};
+use eyre::eyre;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Clear,
+    // A blizzard at the start, blowing in this direction.
+    Blizzard(isize, isize),
+}
+
+pub struct Valley {
+    map: Grid<Tile>,
+    start: Pos,
+    end: Pos,
+    // The blizzards all repeat after this many minutes.
+    period: usize,
+    show_route: bool,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Valley {
+    // Whether `pos` is free of walls and blizzards at `time`. Rather than move
+    // the blizzards, look back along each direction to where a blizzard would
+    // have had to start to be here now.
+    fn is_clear(&self, (x, y): Pos, time: usize) -> bool {
+        if self.map[(x, y)] == Tile::Wall {
+            return false;
+        }
+        if y == 0 || y == self.map.height() - 1 {
+            // The entrance and exit, which no blizzard reaches.
+            return true;
+        }
+        let (width, height) = (self.map.width() - 2, self.map.height() - 2);
+        let (x, y) = (x - 1, y - 1);
+        let (dx, dy) = (time % width, time % height);
+        let started =
+            |x: usize, y: usize, (dx, dy)| self.map[(x + 1, y + 1)] == Tile::Blizzard(dx, dy);
+        !(started((x + width - dx) % width, y, (1, 0))
+            || started((x + dx) % width, y, (-1, 0))
+            || started(x, (y + height - dy) % height, (0, 1))
+            || started(x, (y + dy) % height, (0, -1)))
+    }
+
+    // Finds the quickest way from `from` to `to` setting off at `time`,
+    // returning the position at each minute until arriving.
+    fn cross(&self, from: Pos, to: Pos, time: usize) -> eyre::Result<Vec<Pos>> {
+        let mut frontier = BTreeSet::from([from]);
+        let mut seen = BTreeSet::from([(from, time % self.period)]);
+        // Where each position was reached from, minute by minute.
+        let mut came_from: Vec<BTreeMap<Pos, Pos>> = Vec::new();
+        let mut time = time;
+        while !frontier.contains(&to) {
+            time += 1;
+            let mut steps = BTreeMap::new();
+            for pos in &frontier {
+                let moves = std::iter::once(*pos).chain(self.map.neighbours4(*pos));
+                for next in moves {
+                    if self.is_clear(next, time) && seen.insert((next, time % self.period)) {
+                        steps.insert(next, *pos);
+                    }
+                }
+            }
+            if steps.is_empty() {
+                return Err(eyre!("No way from {from:?} to {to:?}"));
+            }
+            frontier = steps.keys().copied().collect();
+            came_from.push(steps);
+        }
+        let mut route = vec![to];
+        for steps in came_from.iter().rev() {
+            route.push(steps[route.last().expect("route has a start")]);
+        }
+        route.reverse();
+        Ok(route)
+    }
+
+    // Makes each crossing in turn, returning the minute of arrival.
+    fn trip(&self, legs: &[(Pos, Pos)]) -> eyre::Result<usize> {
+        let mut time = 0;
+        for (from, to) in legs {
+            let route = self.cross(*from, *to, time)?;
+            if self.show_route {
+                for (minute, step) in route.windows(2).enumerate() {
+                    diag!(
+                        "Minute {}: {}",
+                        time + minute + 1,
+                        describe(step[0], step[1])
+                    );
+                }
+            }
+            time += route.len() - 1;
+        }
+        Ok(time)
+    }
+}
+
+fn describe(from: Pos, to: Pos) -> String {
+    let direction = match (
+        to.0 as isize - from.0 as isize,
+        to.1 as isize - from.1 as isize,
+    ) {
+        (0, 0) => return "wait".to_owned(),
+        (1, _) => "right",
+        (-1, _) => "left",
+        (_, 1) => "down",
+        _ => "up",
+    };
+    format!("move {direction} to {to:?}")
+}
+
+// The only clear tile on row `y`, pointing at the row if there isn't one.
+fn gap(map: &Grid<Tile>, input: &str, y: usize) -> Result<Pos, ParseError> {
+    let line = input.lines().nth(y).unwrap_or_default();
+    let mut gaps = (0..map.width()).filter(|x| map[(*x, y)] != Tile::Wall);
+    match (gaps.next(), gaps.next()) {
+        (Some(x), None) if map[(x, y)] == Tile::Clear => Ok((x, y)),
+        (_, Some(x)) => Err(ParseError::new(
+            y + 1,
+            x + 1,
+            line,
+            "expected one gap in the wall",
+        )),
+        _ => Err(ParseError::new(
+            y + 1,
+            1,
+            line,
+            "expected a gap in the wall",
+        )),
+    }
+}
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Input = Valley;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "show-route",
+        default: "false",
+        help: "Print each move of the route taken",
+    }];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Self::Input> {
+        let map = Grid::parse(input, |c| match c {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Clear),
+            '>' => Ok(Tile::Blizzard(1, 0)),
+            '<' => Ok(Tile::Blizzard(-1, 0)),
+            'v' => Ok(Tile::Blizzard(0, 1)),
+            '^' => Ok(Tile::Blizzard(0, -1)),
+            _ => Err(eyre!("expected '#', '.' or a blizzard, found {c:?}")),
+        })?;
+        if map.width() < 3 || map.height() < 3 {
+            return Err(eyre!("The valley needs walls around at least one tile"));
+        }
+        let (width, height) = (map.width() - 2, map.height() - 2);
+        // Blizzards wrap within the walls, so any on the way in or out would
+        // block them forever.
+        for (x, y) in map.positions() {
+            if let Tile::Blizzard(_, dy) = map[(x, y)] {
+                let in_gap_column =
+                    map[(x, 0)] != Tile::Wall || map[(x, map.height() - 1)] != Tile::Wall;
+                if x == 0 || x > width || y == 0 || y > height || (dy != 0 && in_gap_column) {
+                    let line = input.lines().nth(y).unwrap_or_default();
+                    return Err(
+                        ParseError::new(y + 1, x + 1, line, "blizzard can't wrap here").into(),
+                    );
+                }
+            }
+        }
+        // The way in and out are the only gaps, and must be between the sides.
+        for y in 0..map.height() {
+            if let Some(x) = [0, width + 1]
+                .into_iter()
+                .find(|x| map[(*x, y)] != Tile::Wall)
+            {
+                let line = input.lines().nth(y).unwrap_or_default();
+                return Err(
+                    ParseError::new(y + 1, x + 1, line, "expected '#' for the wall").into(),
+                );
+            }
+        }
+        Ok(Valley {
+            start: gap(&map, input, 0)?,
+            end: gap(&map, input, map.height() - 1)?,
+            period: width * height / gcd(width, height),
+            show_route: params.get("show-route")?,
+            map,
+        })
+    }
+
+    fn part1(valley: &Self::Input) -> eyre::Result<usize> {
+        valley.trip(&[(valley.start, valley.end)])
+    }
+
+    fn part2(valley: &Self::Input) -> eyre::Result<usize> {
+        // Back for the snacks, then out again.
+        valley.trip(&[
+            (valley.start, valley.end),
+            (valley.end, valley.start),
+            (valley.start, valley.end),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str) -> ParseError {
+        let Err(err) = Day24::parse(input) else {
+            panic!("parsed {input:?}");
+        };
+        err.downcast::<ParseError>().unwrap()
+    }
+
+    #[test]
+    fn blizzards_repeat() {
+        let valley =
+            Day24::parse("#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#\n")
+                .unwrap();
+        assert_eq!(valley.period, 5);
+        assert!(!valley.is_clear((1, 2), 0));
+        assert!(valley.is_clear((4, 2), 2));
+        assert!(!valley.is_clear((4, 2), 3));
+        assert!(!valley.is_clear((1, 2), 5));
+        // The one blowing down wraps round to the top.
+        assert!(!valley.is_clear((4, 5), 1));
+        assert!(!valley.is_clear((4, 1), 2));
+        assert!(!valley.is_clear((0, 1), 0));
+        assert!(valley.is_clear((1, 0), 3));
+
+        let valley = Day24::parse(include_str!("../demo.txt")).unwrap();
+        for pos in valley.map.positions() {
+            for time in 0..valley.period {
+                assert_eq!(
+                    valley.is_clear(pos, time),
+                    valley.is_clear(pos, time + 3 * valley.period)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn no_way() {
+        // The blizzards fill the only row between the gaps.
+        let valley = Day24::parse("#.###\n#>>>#\n###.#\n").unwrap();
+        let err = Day24::part1(&valley).unwrap_err();
+        assert_eq!(err.to_string(), "No way from (1, 0) to (3, 2)");
+    }
+
+    #[test]
+    fn walls_all_round() {
+        let err = parse_error("#.###\n.>..#\n#...#\n###.#\n");
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected '#' for the wall");
+        let err = parse_error(".####\n#...#\n###.#\n");
+        assert_eq!((err.line, err.column), (1, 1));
+        let err = parse_error("#.#.#\n#...#\n###.#\n");
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.message, "expected one gap in the wall");
+    }
+}
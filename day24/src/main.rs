@@ -1,11 +1,11 @@
-use std::io::{self, BufRead};
+use day24::Day24;
 
 fn main() -> eyre::Result<()> {
-    let sum = 0;
-    for line in io::stdin().lock().lines() {
-        let line = line?;
-        print!("{}", line);
-    }
-    println!("Sum: {}", sum);
-    Ok(())
+    aoc_common::cli::run::<Day24>(
+        env!("CARGO_MANIFEST_DIR"),
+        &[
+            "Minutes to reach the goal",
+            "Minutes there, back and there again",
+        ],
+    )
 }
@@ -0,0 +1,317 @@
+//! Numbers written in a balanced base, where an odd radix `R` has digits
+//! running from `-(R / 2)` to `R / 2` instead of from 0. SNAFU is balanced
+//! base 5, written with the digits `=-012`.
+
+use std::{
+    fmt::{self, Display},
+    iter::Sum,
+    ops::Add,
+    str::FromStr,
+};
+
+use aoc_common::{parser::Line, ParseError};
+
+/// A number in balanced base `R`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Balanced<const R: usize> {
+    // Least significant first, with no leading zeros, so zero has no digits.
+    digits: Vec<i8>,
+}
+
+pub type Snafu = Balanced<5>;
+
+impl<const R: usize> Balanced<R> {
+    // The largest digit. Evaluating this checks the radix is usable.
+    const HALF: i8 = {
+        assert!(
+            R % 2 == 1 && R >= 3 && R <= 255,
+            "radix must be odd, from 3 to 255"
+        );
+        (R / 2) as i8
+    };
+
+    fn from_digits(mut digits: Vec<i8>) -> Balanced<R> {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Balanced { digits }
+    }
+
+    pub fn from_i128(mut value: i128) -> Balanced<R> {
+        let radix = R as i128;
+        let mut digits = Vec::new();
+        while value != 0 {
+            let mut digit = value.rem_euclid(radix);
+            value = value.div_euclid(radix);
+            if digit > Self::HALF as i128 {
+                digit -= radix;
+                value += 1;
+            }
+            digits.push(digit as i8);
+        }
+        Balanced { digits }
+    }
+
+    /// The value, or `None` if it doesn't fit in an `i128`.
+    pub fn to_i128(&self) -> Option<i128> {
+        let radix = R as i128;
+        self.digits.iter().rev().try_fold(0i128, |acc, digit| {
+            let digit = *digit as i128;
+            match acc.checked_mul(radix) {
+                Some(shifted) => shifted.checked_add(digit),
+                // Near the ends of the range the shifted value can overflow
+                // even though adding the digit brings it back, so borrow one
+                // from the digit first.
+                None => (acc + digit.signum())
+                    .checked_mul(radix)?
+                    .checked_add(digit - digit.signum() * radix),
+            }
+        })
+    }
+
+    /// The value, or `None` if it doesn't fit in an `i64`.
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128()?.try_into().ok()
+    }
+}
+
+impl<const R: usize> From<i64> for Balanced<R> {
+    fn from(value: i64) -> Balanced<R> {
+        Balanced::from_i128(value.into())
+    }
+}
+
+impl<const R: usize> From<i128> for Balanced<R> {
+    fn from(value: i128) -> Balanced<R> {
+        Balanced::from_i128(value)
+    }
+}
+
+// Adds digit by digit, carrying whenever a column leaves the digit range.
+impl<const R: usize> Add for &Balanced<R> {
+    type Output = Balanced<R>;
+
+    fn add(self, other: &Balanced<R>) -> Balanced<R> {
+        let (half, radix) = (Balanced::<R>::HALF as i16, R as i16);
+        let len = self.digits.len().max(other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for idx in 0..len {
+            let column = |n: &Balanced<R>| n.digits.get(idx).copied().unwrap_or(0) as i16;
+            let mut digit = column(self) + column(other) + carry;
+            carry = 0;
+            if digit > half {
+                digit -= radix;
+                carry = 1;
+            } else if digit < -half {
+                digit += radix;
+                carry = -1;
+            }
+            digits.push(digit as i8);
+        }
+        digits.push(carry as i8);
+        Balanced::from_digits(digits)
+    }
+}
+
+impl<const R: usize> Add for Balanced<R> {
+    type Output = Balanced<R>;
+
+    fn add(self, other: Balanced<R>) -> Balanced<R> {
+        &self + &other
+    }
+}
+
+impl<const R: usize> Sum for Balanced<R> {
+    fn sum<I: Iterator<Item = Balanced<R>>>(iter: I) -> Balanced<R> {
+        iter.fold(Balanced::default(), |acc, n| &acc + &n)
+    }
+}
+
+/// The characters used to write the digits of balanced base `R`, from the
+/// most negative digit up.
+#[derive(Clone, Debug)]
+pub struct Alphabet<const R: usize> {
+    chars: [char; R],
+}
+
+/// The SNAFU digits, from -2 up to 2.
+pub const SNAFU: Alphabet<5> = Alphabet {
+    chars: ['=', '-', '0', '1', '2'],
+};
+
+impl<const R: usize> Alphabet<R> {
+    pub fn new(chars: [char; R]) -> eyre::Result<Alphabet<R>> {
+        let _ = Balanced::<R>::HALF;
+        for (idx, c) in chars.iter().enumerate() {
+            if chars[..idx].contains(c) {
+                return Err(eyre::eyre!("Digit {c:?} is used twice"));
+            }
+        }
+        Ok(Alphabet { chars })
+    }
+
+    fn value(&self, c: char) -> Option<i8> {
+        let idx = self.chars.iter().position(|d| *d == c)?;
+        // Indexes past 127 don't fit in an i8, though the digit always does.
+        Some((idx as i16 - Balanced::<R>::HALF as i16) as i8)
+    }
+
+    /// Reads a number from the line, consuming every digit in a row.
+    pub fn read(&self, line: &mut Line) -> Result<Balanced<R>, ParseError> {
+        let start = line.clone();
+        let text = line.take_while(|c| self.value(c).is_some());
+        if text.is_empty() {
+            let digits: String = self.chars.iter().collect();
+            return Err(start.expected(&format!("a digit from {digits:?}")));
+        }
+        let digits = text.chars().rev().filter_map(|c| self.value(c)).collect();
+        Ok(Balanced::from_digits(digits))
+    }
+
+    pub fn parse(&self, text: &str) -> Result<Balanced<R>, ParseError> {
+        let mut line = Line::new(1, text);
+        let number = self.read(&mut line)?;
+        line.end()?;
+        Ok(number)
+    }
+
+    pub fn format(&self, number: &Balanced<R>) -> String {
+        let half = Balanced::<R>::HALF as usize;
+        if number.digits.is_empty() {
+            return self.chars[half].to_string();
+        }
+        number
+            .digits
+            .iter()
+            .rev()
+            .map(|d| self.chars[(*d as i16 + half as i16) as usize])
+            .collect()
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", SNAFU.format(self))
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Snafu, ParseError> {
+        SNAFU.parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn snafu_examples() {
+        for (value, snafu) in [
+            (0i64, "0"),
+            (1, "1"),
+            (3, "1="),
+            (8, "2="),
+            (10, "20"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-1, "-"),
+            (-3, "-2"),
+        ] {
+            assert_eq!(Snafu::from(value).to_string(), snafu);
+            assert_eq!(snafu.parse::<Snafu>().unwrap().to_i64(), Some(value));
+        }
+    }
+
+    #[test]
+    fn rejects_bad_digits() {
+        let error = "12=3".parse::<Snafu>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert!("".parse::<Snafu>().is_err());
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let big = Snafu::from(i128::MAX);
+        assert_eq!(big.to_i64(), None);
+        assert_eq!((&big + &Snafu::from(1i64)).to_i128(), None);
+        assert_eq!(Snafu::from(i128::MIN).to_i128(), Some(i128::MIN));
+    }
+
+    #[test]
+    fn rejects_repeated_digits() {
+        assert!(Alphabet::new(['a', 'b', 'a']).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn snafu_round_trips_i64(value: i64) {
+            let snafu = Snafu::from(value);
+            prop_assert_eq!(snafu.to_i64(), Some(value));
+            prop_assert_eq!(snafu.to_string().parse::<Snafu>().unwrap(), snafu);
+        }
+
+        #[test]
+        fn snafu_round_trips_i128(value: i128) {
+            let snafu = Snafu::from(value);
+            prop_assert_eq!(snafu.to_i128(), Some(value));
+            prop_assert_eq!(snafu.to_string().parse::<Snafu>().unwrap(), snafu);
+        }
+
+        #[test]
+        fn snafu_adds_like_integers(a: i64, b: i64) {
+            let sum = &Snafu::from(a) + &Snafu::from(b);
+            prop_assert_eq!(sum.to_i128(), Some(a as i128 + b as i128));
+        }
+
+        #[test]
+        fn parses_whatever_it_formats(text in "[=\\-012]{1,30}") {
+            let snafu: Snafu = text.parse().unwrap();
+            let canonical = text.trim_start_matches('0');
+            let canonical = if canonical.is_empty() { "0" } else { canonical };
+            prop_assert_eq!(snafu.to_string(), canonical);
+        }
+
+        #[test]
+        fn other_alphabets_round_trip(value: i64, a: i64) {
+            let ternary = Alphabet::new(['-', '0', '+']).unwrap();
+            let number = Balanced::<3>::from(value);
+            prop_assert_eq!(ternary.parse(&ternary.format(&number)).unwrap().to_i64(), Some(value));
+
+            let septenary = Alphabet::new(['c', 'b', 'a', '0', '1', '2', '3']).unwrap();
+            let (x, y) = (Balanced::<7>::from(value), Balanced::<7>::from(a));
+            let text = septenary.format(&(&x + &y));
+            prop_assert_eq!(
+                septenary.parse(&text).unwrap().to_i128(),
+                Some(value as i128 + a as i128)
+            );
+        }
+
+        #[test]
+        fn large_radixes_round_trip(value: i128, a: i64, b: i64) {
+            // Digits from the Latin Extended blocks, as there aren't enough
+            // in ASCII.
+            let wide = Alphabet::<255>::new(std::array::from_fn(|i| {
+                char::from_u32(0x100 + i as u32).unwrap()
+            }))
+            .unwrap();
+            let number = Balanced::<255>::from(value);
+            prop_assert_eq!(wide.parse(&wide.format(&number)).unwrap().to_i128(), Some(value));
+
+            let odd = Alphabet::<201>::new(std::array::from_fn(|i| {
+                char::from_u32(0x100 + i as u32).unwrap()
+            }))
+            .unwrap();
+            let sum = &Balanced::<201>::from(a) + &Balanced::<201>::from(b);
+            prop_assert_eq!(
+                odd.parse(&odd.format(&sum)).unwrap().to_i128(),
+                Some(a as i128 + b as i128)
+            );
+        }
+    }
+}
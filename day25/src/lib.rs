@@ -0,0 +1,31 @@
+use aoc_common::{parser::lines, NotImplemented, Params, Solver, Unsolved};
+
+pub mod balanced;
+
+use balanced::{Snafu, SNAFU};
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    // The fuel requirements, in SNAFU.
+    type Input = Vec<Snafu>;
+    type Part1 = Snafu;
+    type Part2 = Unsolved;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        let mut numbers = Vec::new();
+        for mut line in lines(input) {
+            numbers.push(SNAFU.read(&mut line)?);
+            line.end()?;
+        }
+        Ok(numbers)
+    }
+
+    fn part1(numbers: &Self::Input) -> eyre::Result<Snafu> {
+        Ok(numbers.iter().cloned().sum())
+    }
+
+    fn part2(_numbers: &Self::Input) -> eyre::Result<Unsolved> {
+        Err(NotImplemented.into())
+    }
+}
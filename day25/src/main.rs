@@ -1,11 +1,8 @@
-use std::io::{self, BufRead};
+use day25::Day25;
 
 fn main() -> eyre::Result<()> {
-    let sum = 0;
-    for line in io::stdin().lock().lines() {
-        let line = line?;
-        print!("{}", line);
-    }
-    println!("Sum: {}", sum);
-    Ok(())
+    aoc_common::cli::run::<Day25>(
+        env!("CARGO_MANIFEST_DIR"),
+        &["SNAFU number for the console"],
+    )
 }
@@ -0,0 +1,73 @@
+use std::collections::BTreeSet;
+
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+
+fn byte_to_priority(item: &u8) -> i32 {
+    if item.is_ascii_lowercase() {
+        *item as i32 - 'a' as i32 + 1
+    } else {
+        *item as i32 - 'A' as i32 + 27
+    }
+}
+
+fn to_item_priorities(mut line: Line) -> Result<Vec<i32>, ParseError> {
+    let items = line.take_while(|c| c.is_ascii_alphabetic());
+    if !line.is_empty() {
+        return Err(line.expected("an item from a-z or A-Z"));
+    }
+    Ok(items.as_bytes().iter().map(byte_to_priority).collect())
+}
+
+// Finds the single item priority shared by every group of items.
+fn common_priority<'a>(mut groups: impl Iterator<Item = &'a [i32]>) -> eyre::Result<i32> {
+    let first: BTreeSet<i32> = groups
+        .next()
+        .ok_or_else(|| eyre::eyre!("No items to compare"))?
+        .iter()
+        .cloned()
+        .collect();
+    let intersection = groups.fold(first, |acc, group| {
+        let group: BTreeSet<i32> = group.iter().cloned().collect();
+        acc.intersection(&group).cloned().collect()
+    });
+    if intersection.len() != 1 {
+        return Err(eyre::eyre!("Unexpected intersection {:?}", intersection));
+    }
+    Ok(*intersection.iter().next().unwrap())
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    // The item priorities in each rucksack, in order.
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Ok(lines(input)
+            .map(to_item_priorities)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> eyre::Result<i32> {
+        // Each rucksack has two equally sized compartments.
+        rucksacks
+            .iter()
+            .map(|rucksack| {
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                common_priority([first, second].into_iter())
+            })
+            .sum()
+    }
+
+    fn part2(rucksacks: &Self::Input) -> eyre::Result<i32> {
+        rucksacks
+            .chunks(3)
+            .map(|group| common_priority(group.iter().map(|r| r.as_slice())))
+            .sum()
+    }
+}
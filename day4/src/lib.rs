@@ -0,0 +1,61 @@
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+
+// An inclusive range of section ids.
+type Range = (i32, i32);
+
+fn range_to_tuple(line: &mut Line) -> Result<Range, ParseError> {
+    let lower = line.number()?;
+    line.tag("-")?;
+    Ok((lower, line.number()?))
+}
+
+fn line_to_ranges(mut line: Line) -> Result<(Range, Range), ParseError> {
+    let elf1 = range_to_tuple(&mut line)?;
+    line.tag(",")?;
+    let elf2 = range_to_tuple(&mut line)?;
+    line.end()?;
+    Ok((elf1, elf2))
+}
+
+fn contains(range1: Range, range2: Range) -> bool {
+    range1.0 >= range2.0 && range1.1 <= range2.1
+}
+
+fn overlaps(range1: Range, range2: Range) -> bool {
+    (range1.0 >= range2.0 && range1.0 <= range2.1) || (range1.1 >= range2.0 && range1.1 <= range2.1)
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    // The section ranges assigned to each pair of elves.
+    type Input = Vec<(Range, Range)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Ok(lines(input).map(line_to_ranges).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(pairs: &Self::Input) -> eyre::Result<usize> {
+        Ok(pairs
+            .iter()
+            .filter(|(elf1, elf2)| contains(*elf1, *elf2) || contains(*elf2, *elf1))
+            .count())
+    }
+
+    fn part2(pairs: &Self::Input) -> eyre::Result<usize> {
+        Ok(pairs
+            .iter()
+            .filter(|(elf1, elf2)| {
+                contains(*elf1, *elf2)
+                    || contains(*elf2, *elf1)
+                    || overlaps(*elf1, *elf2)
+                    || overlaps(*elf2, *elf1)
+            })
+            .count())
+    }
+}
@@ -0,0 +1,150 @@
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+use eyre::eyre;
+
+fn stack_number(line: &mut Line, stack_count: usize) -> Result<usize, ParseError> {
+    let start = line.clone();
+    let stack = line.number()?;
+    if !(1..=stack_count).contains(&stack) {
+        return Err(start.error(format!("there is no stack {stack}")));
+    }
+    Ok(stack)
+}
+
+fn line_to_moves(mut line: Line, stack_count: usize) -> Result<(usize, usize, usize), ParseError> {
+    line.tag("move ")?;
+    let count = line.number()?;
+    line.tag(" from ")?;
+    let from = stack_number(&mut line, stack_count)?;
+    line.tag(" to ")?;
+    let to = stack_number(&mut line, stack_count)?;
+    line.end()?;
+    Ok((count, from, to))
+}
+
+// Reads one row of the starting drawing, such as "[Z] [M]     [P]", pushing
+// its crates onto the stacks. Trailing empty slots may be left off.
+fn push_crate_row(mut row: Line, crate_stacks: &mut [Vec<char>]) -> Result<(), ParseError> {
+    for (idx, stack) in crate_stacks.iter_mut().enumerate() {
+        if idx > 0 && !row.is_empty() {
+            row.tag(" ")?;
+        }
+        if row.is_empty() {
+            break;
+        }
+        if row.eat("   ") {
+            continue;
+        }
+        row.tag("[")?;
+        let start = row.clone();
+        match row.take_while(|c| c.is_ascii_alphabetic()) {
+            label if label.len() == 1 => stack.extend(label.chars()),
+            _ => return Err(start.expected("a single crate letter")),
+        }
+        row.tag("]")?;
+    }
+    row.end()
+}
+
+pub struct Cargo {
+    crate_stacks: Vec<Vec<char>>,
+    // Each move is (count, from, to) with 1-based stack numbers.
+    moves: Vec<(usize, usize, usize)>,
+}
+
+#[derive(Clone, Copy)]
+enum CrateMover {
+    // Moves one crate at a time, reversing their order.
+    Model9000,
+    // Moves all the crates at once, keeping their order.
+    Model9001,
+}
+
+fn top_crates(cargo: &Cargo, crane: CrateMover) -> eyre::Result<String> {
+    let mut crate_stacks = cargo.crate_stacks.clone();
+    // Processing sequence of moves.
+    for (idx, (count, from, to)) in cargo.moves.iter().copied().enumerate() {
+        let from = &mut crate_stacks[from - 1];
+        if count > from.len() {
+            return Err(eyre!(
+                "Move {} takes {count} crates from a stack of {}",
+                idx + 1,
+                from.len()
+            ));
+        }
+        let mut crates: Vec<char> = from.drain(from.len() - count..from.len()).collect();
+        if let CrateMover::Model9000 = crane {
+            crates.reverse();
+        }
+        let to = &mut crate_stacks[to - 1];
+        to.append(&mut crates)
+    }
+    Ok(crate_stacks
+        .iter()
+        .filter_map(|stack| stack.last())
+        .collect())
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Cargo;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Cargo> {
+        let mut lines = lines(input);
+        // The drawing of the stacks is read top down, then ends with a row
+        // numbering them.
+        let mut crate_rows = Vec::new();
+        let mut stack_count = 0;
+        loop {
+            let Some(mut line) = lines.next() else {
+                return Err(
+                    ParseError::end_of_input(crate_rows.len() + 1, "the stack numbers").into(),
+                );
+            };
+            if line.text().contains('[') {
+                crate_rows.push(line);
+                continue;
+            }
+            while !line.is_empty() {
+                line.take_while(|c| c == ' ');
+                let start = line.clone();
+                let stack: usize = line.number()?;
+                if stack != stack_count + 1 {
+                    return Err(start.expected(&format!("stack {}", stack_count + 1)).into());
+                }
+                stack_count = stack;
+                line.take_while(|c| c == ' ');
+            }
+            break;
+        }
+        let mut crate_stacks: Vec<Vec<char>> = vec![Vec::new(); stack_count];
+        for row in crate_rows.into_iter().rev() {
+            push_crate_row(row, &mut crate_stacks)?;
+        }
+        match lines.next() {
+            Some(line) if line.is_empty() => {}
+            Some(line) => return Err(line.expected("a blank line").into()),
+            None => {}
+        }
+        let moves = lines
+            .map(|line| line_to_moves(line, stack_count))
+            .collect::<Result<_, _>>()?;
+        Ok(Cargo {
+            crate_stacks,
+            moves,
+        })
+    }
+
+    fn part1(cargo: &Cargo) -> eyre::Result<String> {
+        top_crates(cargo, CrateMover::Model9000)
+    }
+
+    fn part2(cargo: &Cargo) -> eyre::Result<String> {
+        top_crates(cargo, CrateMover::Model9001)
+    }
+}
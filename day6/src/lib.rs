@@ -0,0 +1,51 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use aoc_common::{Params, Solver};
+
+const PACKET_MARKER_LENGTH: usize = 4;
+const MESSAGE_MARKER_LENGTH: usize = 14;
+
+fn find_unique_offset(bytes: &[u8], header_length: usize) -> eyre::Result<usize> {
+    let mut last = VecDeque::new();
+    for (idx, byte) in bytes.iter().enumerate() {
+        if last.len() < header_length - 1 {
+            last.push_back(byte);
+            continue;
+        }
+        last.push_back(byte);
+        if last.len() > header_length {
+            last.pop_front();
+        }
+        assert!(last.len() == header_length);
+        let byte_set: BTreeSet<_> = last.iter().collect();
+        if byte_set.len() == header_length {
+            return Ok(idx + 1);
+        }
+    }
+    Err(eyre::eyre!("Failed to find start"))
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    // The datastream buffer.
+    type Input = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or_else(|| eyre::eyre!("No input"))?;
+        Ok(line.as_bytes().to_vec())
+    }
+
+    fn part1(buffer: &Self::Input) -> eyre::Result<usize> {
+        find_unique_offset(buffer, PACKET_MARKER_LENGTH)
+    }
+
+    fn part2(buffer: &Self::Input) -> eyre::Result<usize> {
+        find_unique_offset(buffer, MESSAGE_MARKER_LENGTH)
+    }
+}
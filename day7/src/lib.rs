@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use aoc_common::{parser::lines, Param, Params, Solver};
+
+#[derive(Debug)]
+pub struct Directory {
+    child_dirs: BTreeMap<String, Directory>,
+    children: BTreeMap<String, usize>,
+}
+
+impl Directory {
+    fn new() -> Directory {
+        Directory {
+            child_dirs: BTreeMap::new(),
+            children: BTreeMap::new(),
+        }
+    }
+
+    fn lookup_path(&mut self, path: &[String]) -> &mut Directory {
+        if path.is_empty() {
+            self
+        } else {
+            if !self.child_dirs.contains_key(&path[0]) {
+                self.child_dirs.insert(path[0].clone(), Directory::new());
+            }
+            let child_dir = self.child_dirs.get_mut(&path[0]).unwrap();
+            child_dir.lookup_path(&path[1..path.len()])
+        }
+    }
+
+    fn add_file(&mut self, name: &str, size: usize) {
+        self.children.insert(name.to_string(), size);
+    }
+
+    fn get_total_size(&self) -> usize {
+        let child_size: usize = self.children.values().sum();
+        let child_dir_size: usize = self.child_dirs.values().map(|d| d.get_total_size()).sum();
+        child_dir_size + child_size
+    }
+}
+
+fn get_sum(dir: &Directory, limit: usize) -> usize {
+    let mut dir_sum = 0;
+    for child_dir in dir.child_dirs.values() {
+        dir_sum += get_sum(child_dir, limit);
+    }
+    let this_total = dir.get_total_size();
+    if this_total <= limit {
+        dir_sum += this_total;
+    }
+    dir_sum
+}
+
+fn find_smallest(dir: &Directory, minimum_size: usize) -> Option<usize> {
+    let this_size = dir.get_total_size();
+    if this_size < minimum_size {
+        return None;
+    }
+    let mut smallest = Some(this_size);
+    for child_dir in dir.child_dirs.values() {
+        if let Some(s) = find_smallest(child_dir, minimum_size) {
+            if Some(s) < smallest {
+                smallest = Some(s)
+            }
+        }
+    }
+    smallest
+}
+
+pub struct Filesystem {
+    root: Directory,
+    size_limit: usize,
+    disk_size: usize,
+    space_needed: usize,
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = Filesystem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "size-limit",
+            default: "100000",
+            help: "Largest directory to add up in part 1",
+        },
+        Param {
+            name: "disk-size",
+            default: "70000000",
+            help: "Total size of the filesystem",
+        },
+        Param {
+            name: "space-needed",
+            default: "30000000",
+            help: "Free space needed for the update",
+        },
+    ];
+
+    fn parse_with(input: &str, params: &Params) -> eyre::Result<Filesystem> {
+        let mut root = Directory::new();
+        let mut current_path = VecDeque::<String>::new();
+        for mut line in lines(input) {
+            if line.eat("$ ") {
+                if line.eat("ls") {
+                    line.end()?;
+                    continue;
+                }
+                line.tag("cd ")?;
+                let args = line.take_rest();
+                if args == "/" {
+                    current_path.clear();
+                } else if args == ".." {
+                    if !current_path.is_empty() {
+                        current_path.pop_back();
+                    }
+                } else {
+                    current_path.push_back(args.to_string());
+                }
+            } else {
+                // Should be a directory listing.
+                if line.eat("dir ") {
+                    // Ignore directories
+                } else {
+                    let size: usize = line.number()?;
+                    line.tag(" ")?;
+                    let name = line.take_rest();
+                    current_path.make_contiguous();
+                    let cwd = root.lookup_path(current_path.as_slices().0);
+                    cwd.add_file(name, size);
+                }
+            }
+        }
+        Ok(Filesystem {
+            root,
+            size_limit: params.get("size-limit")?,
+            disk_size: params.get("disk-size")?,
+            space_needed: params.get("space-needed")?,
+        })
+    }
+
+    fn part1(fs: &Filesystem) -> eyre::Result<usize> {
+        Ok(get_sum(&fs.root, fs.size_limit))
+    }
+
+    fn part2(fs: &Filesystem) -> eyre::Result<usize> {
+        let current_free_space = fs
+            .disk_size
+            .checked_sub(fs.root.get_total_size())
+            .ok_or_else(|| eyre::eyre!("The files don't fit on the disk"))?;
+        let extra_free_needed = fs.space_needed.saturating_sub(current_free_space);
+        find_smallest(&fs.root, extra_free_needed)
+            .ok_or_else(|| eyre::eyre!("No directory is large enough to free"))
+    }
+}
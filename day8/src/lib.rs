@@ -0,0 +1,59 @@
+use aoc_common::{grid::ORTHOGONAL, Grid, Params, Solver};
+
+fn parse_height(c: char) -> eyre::Result<i32> {
+    c.to_digit(10)
+        .map(|h| h as i32)
+        .ok_or_else(|| eyre::eyre!("Unexpected tree height: {c}"))
+}
+
+fn is_visible(trees: &Grid<i32>, pos: (usize, usize)) -> bool {
+    let height = trees[pos];
+    ORTHOGONAL
+        .iter()
+        .any(|dir| trees.ray(pos, *dir).all(|p| trees[p] < height))
+}
+
+fn scenic_score(trees: &Grid<i32>, pos: (usize, usize)) -> usize {
+    let height = trees[pos];
+    ORTHOGONAL
+        .iter()
+        .map(|dir| {
+            let mut score = 0;
+            for p in trees.ray(pos, *dir) {
+                score += 1;
+                if trees[p] >= height {
+                    break;
+                }
+            }
+            score
+        })
+        .product()
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    // The height of each tree.
+    type Input = Grid<i32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Grid::parse(input, parse_height)
+    }
+
+    fn part1(trees: &Self::Input) -> eyre::Result<usize> {
+        Ok(trees
+            .positions()
+            .filter(|pos| is_visible(trees, *pos))
+            .count())
+    }
+
+    fn part2(trees: &Self::Input) -> eyre::Result<usize> {
+        trees
+            .positions()
+            .map(|pos| scenic_score(trees, pos))
+            .max()
+            .ok_or_else(|| eyre::eyre!("No trees"))
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeSet;
+
+use aoc_common::{
+    parser::{lines, Line},
+    Params, ParseError, Solver,
+};
+
+#[derive(Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn line_to_move(mut line: Line) -> Result<(Direction, i32), ParseError> {
+    let direction = line.one_of(&[
+        ("U", Direction::Up),
+        ("D", Direction::Down),
+        ("L", Direction::Left),
+        ("R", Direction::Right),
+    ])?;
+    line.tag(" ")?;
+    let count = line.number()?;
+    line.end()?;
+    Ok((direction, count))
+}
+
+fn count_tail_positions(moves: &[(Direction, i32)], knot_count: usize) -> usize {
+    let mut tail_positions = BTreeSet::<(i32, i32)>::new();
+    let mut knots: Vec<(i32, i32)> = (0..knot_count).map(|_| (0, 0)).collect();
+    tail_positions.insert(knots[knots.len() - 1]);
+    for (direction, count) in moves {
+        let delta = match direction {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        };
+        for _ in 0..*count {
+            knots[0] = (knots[0].0 + delta.0, knots[0].1 + delta.1);
+            for i in 1..knots.len() {
+                if knots[i].0 < knots[i - 1].0 - 1
+                    || knots[i].0 > knots[i - 1].0 + 1
+                    || knots[i].1 < knots[i - 1].1 - 1
+                    || knots[i].1 > knots[i - 1].1 + 1
+                {
+                    // Tail needs to move closer to the head.
+                    if knots[i].0 > knots[i - 1].0 {
+                        knots[i].0 -= 1;
+                    }
+                    if knots[i].0 < knots[i - 1].0 {
+                        knots[i].0 += 1;
+                    }
+                    if knots[i].1 > knots[i - 1].1 {
+                        knots[i].1 -= 1;
+                    }
+                    if knots[i].1 < knots[i - 1].1 {
+                        knots[i].1 += 1;
+                    }
+                }
+            }
+            tail_positions.insert(knots[knots.len() - 1]);
+        }
+    }
+    tail_positions.len()
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Vec<(Direction, i32)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_with(input: &str, _params: &Params) -> eyre::Result<Self::Input> {
+        Ok(lines(input).map(line_to_move).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(moves: &Self::Input) -> eyre::Result<usize> {
+        Ok(count_tail_positions(moves, 2))
+    }
+
+    fn part2(moves: &Self::Input) -> eyre::Result<usize> {
+        Ok(count_tail_positions(moves, 10))
+    }
+}